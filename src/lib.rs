@@ -13,6 +13,19 @@
 //! let replay = Replay::load(br).unwrap();
 //! println!("{:#?}", replay);
 //! ```
+//!
+//! # Loading selected blocks
+//! Indexing the replay and loading only the Notes block:
+//! ```no_run
+//! use bsor::prelude::*;
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! let br = &mut BufReader::new(File::open("example.bsor").unwrap());
+//! let replay_index = ReplayIndex::index(br).unwrap();
+//! let notes = replay_index.notes.load(br).unwrap();
+//! println!("{} notes", notes.len());
+//! ```
 
 pub mod prelude;
 pub mod replay;
@@ -27,7 +40,6 @@ pub(crate) mod tests_util {
     };
     use crate::replay::pause::{Pause, Pauses};
     use crate::replay::wall::{Wall, Walls};
-    use crate::replay::BSOR_MAGIC;
     use crate::replay::{
         vector::{Vector3, Vector4},
         BlockType, Replay, ReplayFloat, ReplayInt, ReplayLong,
@@ -95,49 +107,12 @@ pub(crate) mod tests_util {
         append_position_and_rotation(vec, &frame.right_hand);
     }
 
-    pub(crate) fn append_note_cut_info(vec: &mut Vec<u8>, cut_info: &NoteCutInfo) {
-        vec.push(cut_info.speed_ok as u8);
-        vec.push(cut_info.direction_ok as u8);
-        vec.push(cut_info.saber_type_ok as u8);
-        vec.push(cut_info.was_cut_too_soon as u8);
-        vec.append(&mut ReplayFloat::to_le_bytes(cut_info.saber_speed).to_vec());
-        append_vector3(vec, &cut_info.saber_dir);
-
-        let saber_type: u8 = cut_info.saber_type.try_into().unwrap();
-        vec.append(&mut ReplayInt::to_le_bytes(saber_type as ReplayInt).to_vec());
-        vec.append(&mut ReplayFloat::to_le_bytes(cut_info.time_deviation).to_vec());
-        vec.append(&mut ReplayFloat::to_le_bytes(cut_info.cut_dir_deviation).to_vec());
-        append_vector3(vec, &cut_info.cut_point);
-        append_vector3(vec, &cut_info.cut_normal);
-        vec.append(&mut ReplayFloat::to_le_bytes(cut_info.cut_distance_to_center).to_vec());
-        vec.append(&mut ReplayFloat::to_le_bytes(cut_info.cut_angle).to_vec());
-        vec.append(&mut ReplayFloat::to_le_bytes(cut_info.before_cut_rating).to_vec());
-        vec.append(&mut ReplayFloat::to_le_bytes(cut_info.after_cut_rating).to_vec());
-    }
-
-    pub(crate) fn append_note(vec: &mut Vec<u8>, note: &Note) {
-        let scoring_type_u8: u8 = NoteScoringType::try_into(note.scoring_type).unwrap();
-        let color_type_u8: u8 = ColorType::try_into(note.color_type).unwrap();
-        let cut_direction_u8: u8 = CutDirection::try_into(note.cut_direction).unwrap();
-
-        let note_id: ReplayInt = scoring_type_u8 as ReplayInt * 10000
+    pub(crate) fn pack_note_id(note: &Note) -> ReplayInt {
+        note.scoring_type as ReplayInt * 10000
             + note.line_idx as ReplayInt * 1000
             + note.line_layer as ReplayInt * 100
-            + color_type_u8 as ReplayInt * 10
-            + cut_direction_u8 as ReplayInt;
-        vec.append(&mut ReplayInt::to_le_bytes(note_id).to_vec());
-        vec.append(&mut ReplayFloat::to_le_bytes(note.event_time).to_vec());
-        vec.append(&mut ReplayFloat::to_le_bytes(note.spawn_time).to_vec());
-
-        let event_type: u8 = note.event_type.try_into().unwrap();
-        vec.append(&mut ReplayInt::to_le_bytes(event_type as ReplayInt).to_vec());
-
-        match note.event_type {
-            NoteEventType::Good | NoteEventType::Bad => {
-                append_note_cut_info(vec, note.cut_info.as_ref().unwrap())
-            }
-            _ => {}
-        }
+            + note.color_type as ReplayInt * 10
+            + note.cut_direction as ReplayInt
     }
 
     pub(crate) fn append_wall(vec: &mut Vec<u8>, wall: &Wall) {
@@ -288,7 +263,8 @@ pub(crate) mod tests_util {
             _ => None,
         };
 
-        Note {
+        let mut note = Note {
+            note_id: 0,
             scoring_type: NoteScoringType::Normal,
             line_idx: random::<u8>() % 4,
             line_layer: random::<u8>() % 3,
@@ -298,7 +274,51 @@ pub(crate) mod tests_util {
             spawn_time: random::<ReplayTime>() * 100.0,
             event_type,
             cut_info,
-        }
+        };
+        note.note_id = pack_note_id(&note);
+
+        note
+    }
+
+    pub(crate) fn generate_note_at(
+        event_type: NoteEventType,
+        color_type: ColorType,
+        time: ReplayTime,
+    ) -> Note {
+        let mut note = Note {
+            color_type,
+            event_time: time,
+            spawn_time: time,
+            ..generate_random_note(event_type)
+        };
+        note.note_id = pack_note_id(&note);
+
+        note
+    }
+
+    pub(crate) fn generate_bomb_with_id(note_id: ReplayInt) -> Note {
+        let mut buf = ReplayInt::to_le_bytes(note_id).to_vec();
+        buf.append(&mut ReplayFloat::to_le_bytes(random::<ReplayTime>() * 100.0).to_vec());
+        buf.append(&mut ReplayFloat::to_le_bytes(random::<ReplayTime>() * 100.0).to_vec());
+        buf.append(&mut ReplayInt::to_le_bytes(ReplayInt::from(NoteEventType::Bomb)).to_vec());
+
+        Note::load(&mut buf.as_slice()).unwrap()
+    }
+
+    pub(crate) fn generate_good_note_with_ratings(
+        before_cut_rating: ReplayFloat,
+        after_cut_rating: ReplayFloat,
+        cut_distance_to_center: ReplayFloat,
+    ) -> Note {
+        let mut note = generate_random_note(NoteEventType::Good);
+        note.cut_info = Some(NoteCutInfo {
+            before_cut_rating,
+            after_cut_rating,
+            cut_distance_to_center,
+            ..generate_random_note_cut_info()
+        });
+
+        note
     }
 
     pub(crate) fn generate_random_frame() -> Frame {
@@ -337,20 +357,8 @@ pub(crate) mod tests_util {
     }
 
     pub(crate) fn get_replay_buffer(replay: &Replay) -> Result<Vec<u8>> {
-        // header
-        let mut buf = ReplayInt::to_le_bytes(BSOR_MAGIC).to_vec();
-        buf.push(replay.version);
-
-        // info
-        let info_id = BlockType::Info.try_into()?;
-        buf.append(&mut Vec::from([info_id]));
-        append_info(&mut buf, &replay.info)?;
-
-        buf.append(&mut get_frames_buffer(&replay.frames)?);
-        buf.append(&mut get_notes_buffer(&replay.notes)?);
-        buf.append(&mut get_walls_buffer(&replay.walls)?);
-        buf.append(&mut get_heights_buffer(&replay.heights)?);
-        buf.append(&mut get_pauses_buffer(&replay.pauses)?);
+        let mut buf = Vec::new();
+        replay.write(&mut buf)?;
 
         Ok(buf)
     }
@@ -368,13 +376,8 @@ pub(crate) mod tests_util {
     }
 
     pub(crate) fn get_notes_buffer(notes: &Vec<Note>) -> Result<Vec<u8>> {
-        let notes_id = BlockType::Notes.try_into()?;
-        let mut buf: Vec<u8> = Vec::from([notes_id]);
-
-        buf.append(&mut ReplayInt::to_le_bytes(notes.len() as ReplayInt).to_vec());
-        for f in notes.iter() {
-            append_note(&mut buf, &f);
-        }
+        let mut buf = Vec::new();
+        Notes::new(notes.clone()).write(&mut buf)?;
 
         Ok(buf)
     }
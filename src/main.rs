@@ -4,8 +4,7 @@ use std::io::BufReader;
 
 fn main() {
     {
-        let br = &mut BufReader::new(File::open("example.bsor").unwrap());
-        let replay = Replay::load(br).unwrap();
+        let replay = Replay::from_path("example.bsor").unwrap();
         println!("{:#?}", replay.info);
     }
 
@@ -1,3 +1,43 @@
 //! The prelude contains all commonly used components of the crate
+//!
+//! ```no_run
+//! use bsor::prelude::*;
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! let br = &mut BufReader::new(File::open("example.bsor").unwrap());
+//! let mut options = LoadOptions::default();
+//! options.lenient = true;
+//! let (replay, warnings): (Replay, Vec<LoadWarning>) =
+//!     Replay::load_with_options(br, &options).unwrap();
+//!
+//! let streams = replay
+//!     .notes
+//!     .pattern_segments()
+//!     .into_iter()
+//!     .filter(|s: &PatternSegment| s.kind == PatternKind::Stream)
+//!     .count();
+//! let red_notes = replay.notes.by_color(ColorType::Red).count();
+//! println!("{} streams, {} red notes, {} warnings", streams, red_notes, warnings.len());
+//! ```
+pub use crate::replay::columns::ReplayColumns;
+pub use crate::replay::device::{Controller, Hmd};
 pub use crate::replay::error::BsorError;
-pub use crate::replay::{LoadBlock, Replay, ReplayIndex, Result};
+pub use crate::replay::file::ReplayFile;
+pub use crate::replay::frame::{FpsStats, Frame, FrameIterator, Frames};
+pub use crate::replay::height::{Height, Heights};
+pub use crate::replay::info::{Info, InfoView};
+pub use crate::replay::modifier::Modifier;
+pub use crate::replay::note::{
+    ColorType, CutDirection, MatchResult, Note, NoteComparison, NoteCounts, NoteCutInfo,
+    NoteEventType, NoteScore, NoteScoringType, Notes, PatternKind, PatternSegment,
+};
+pub use crate::replay::pause::{Pause, Pauses};
+// time::Duration is left out, as it would clash with std::time::Duration in glob imports
+pub use crate::replay::time::SongTime;
+pub use crate::replay::wall::{Wall, Walls};
+pub use crate::replay::writer::ReplayWriter;
+pub use crate::replay::{
+    BlockIndex, BlockOffsets, BlockType, LoadBlock, LoadOptions, LoadWarning, LoadedBlock, Replay,
+    ReplayIndex, Result, ScoringEvent, ValidationWarning, BSOR_MAGIC,
+};
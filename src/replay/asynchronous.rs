@@ -0,0 +1,300 @@
+//! Async loading of a replay from [tokio::io::AsyncRead] (requires `async` feature)
+//!
+//! Bytes of each block are read asynchronously and then parsed by the same loaders as used by [Replay::load()]
+//!
+//! # Example
+//! ```no_run
+//! use bsor::prelude::*;
+//!
+//! # async fn example(bytes: Vec<u8>) -> Result<()> {
+//! let replay = Replay::load_async(&mut bytes.as_slice()).await?;
+//! println!("{} notes", replay.notes.len());
+//! # Ok(())
+//! # }
+//! ```
+use super::frame::{Frame, Frames};
+use super::header::Header;
+use super::height::{Height, Heights};
+use super::info::{Info, MAX_INFO_STRING_LEN};
+use super::note::{Note, NoteCutInfo, NoteEventType, Notes};
+use super::pause::{Pause, Pauses};
+use super::wall::{Wall, Walls};
+use super::{
+    BsorError, GetStaticBlockSize, LoadOptions, LoadWarning, Replay, ReplayFloat, ReplayInt,
+    Result, DEFAULT_MAX_BLOCK_ITEMS,
+};
+use std::future::poll_fn;
+use std::io::ErrorKind;
+use std::mem::size_of;
+use std::pin::Pin;
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Number of strings in the Info block stored before and after the score
+const INFO_STRINGS: (usize, usize) = (13, 3);
+
+impl Replay {
+    /// Load replay into memory from async reader
+    pub async fn load_async<R: AsyncRead + Unpin>(r: &mut R) -> Result<Replay> {
+        let mut buf = Vec::new();
+        append_bytes(r, &mut buf, Header::get_static_size()).await?;
+        let header = Header::load(&mut buf.as_slice())?;
+
+        let offset = &mut (buf.len() as u64);
+
+        Ok(Replay {
+            version: header.version,
+            info: Info::load_async_at(r, offset).await?,
+            frames: Frames::load_async_at(r, offset).await?,
+            notes: Notes::load_async_at(r, offset).await?,
+            walls: Walls::load_async_at(r, offset).await?,
+            heights: Heights::load_async_at(r, offset).await?,
+            pauses: Pauses::load_async_at(r, offset).await?,
+        })
+    }
+}
+
+impl Info {
+    /// Loads Info block from async reader
+    pub async fn load_async<R: AsyncRead + Unpin>(r: &mut R) -> Result<Info> {
+        Self::load_async_at(r, &mut 0).await
+    }
+
+    async fn load_async_at<R: AsyncRead + Unpin>(r: &mut R, offset: &mut u64) -> Result<Info> {
+        let mut buf = Vec::new();
+        append_bytes(r, &mut buf, size_of::<u8>()).await?;
+
+        for _ in 0..INFO_STRINGS.0 {
+            append_string(r, &mut buf).await?;
+        }
+        append_bytes(r, &mut buf, size_of::<ReplayInt>()).await?;
+        for _ in 0..INFO_STRINGS.1 {
+            append_string(r, &mut buf).await?;
+        }
+        append_bytes(r, &mut buf, size_of::<ReplayFloat>() * 5 + size_of::<u8>()).await?;
+
+        *offset += buf.len() as u64;
+
+        Info::load(&mut buf.as_slice())
+    }
+}
+
+impl Frames {
+    /// Loads Frames block from async reader
+    pub async fn load_async<R: AsyncRead + Unpin>(r: &mut R) -> Result<Frames> {
+        Self::load_async_at(r, &mut 0).await
+    }
+
+    async fn load_async_at<R: AsyncRead + Unpin>(r: &mut R, offset: &mut u64) -> Result<Frames> {
+        let buf = read_static_items_block(r, Frame::get_static_size()).await?;
+
+        parse_block(&buf, offset, Frames::load_with_options)
+    }
+}
+
+impl Notes {
+    /// Loads Notes block from async reader
+    pub async fn load_async<R: AsyncRead + Unpin>(r: &mut R) -> Result<Notes> {
+        Self::load_async_at(r, &mut 0).await
+    }
+
+    async fn load_async_at<R: AsyncRead + Unpin>(r: &mut R, offset: &mut u64) -> Result<Notes> {
+        let mut buf = Vec::new();
+        let count = append_block_header(r, &mut buf).await?;
+
+        for _ in 0..count {
+            append_bytes(r, &mut buf, Note::get_static_size()).await?;
+
+            let event_type = ReplayInt::from_le_bytes(buf[buf.len() - 4..].try_into()?);
+            if let NoteEventType::Good | NoteEventType::Bad = event_type.try_into()? {
+                append_bytes(r, &mut buf, NoteCutInfo::get_static_size()).await?;
+            }
+        }
+
+        parse_block(&buf, offset, Notes::load_with_options)
+    }
+}
+
+impl Walls {
+    /// Loads Walls block from async reader
+    pub async fn load_async<R: AsyncRead + Unpin>(r: &mut R) -> Result<Walls> {
+        Self::load_async_at(r, &mut 0).await
+    }
+
+    async fn load_async_at<R: AsyncRead + Unpin>(r: &mut R, offset: &mut u64) -> Result<Walls> {
+        let buf = read_static_items_block(r, Wall::get_static_size()).await?;
+
+        parse_block(&buf, offset, Walls::load_with_options)
+    }
+}
+
+impl Heights {
+    /// Loads Heights block from async reader
+    pub async fn load_async<R: AsyncRead + Unpin>(r: &mut R) -> Result<Heights> {
+        Self::load_async_at(r, &mut 0).await
+    }
+
+    async fn load_async_at<R: AsyncRead + Unpin>(r: &mut R, offset: &mut u64) -> Result<Heights> {
+        let buf = read_static_items_block(r, Height::get_static_size()).await?;
+
+        parse_block(&buf, offset, Heights::load_with_options)
+    }
+}
+
+impl Pauses {
+    /// Loads Pauses block from async reader
+    pub async fn load_async<R: AsyncRead + Unpin>(r: &mut R) -> Result<Pauses> {
+        Self::load_async_at(r, &mut 0).await
+    }
+
+    async fn load_async_at<R: AsyncRead + Unpin>(r: &mut R, offset: &mut u64) -> Result<Pauses> {
+        let buf = read_static_items_block(r, Pause::get_static_size()).await?;
+
+        parse_block(&buf, offset, Pauses::load_with_options)
+    }
+}
+
+/// Parses bytes of a block starting at given stream offset, then advances the offset past the block
+fn parse_block<'a, T>(
+    buf: &'a [u8],
+    offset: &mut u64,
+    load: impl FnOnce(&mut &'a [u8], u64, &LoadOptions, &mut Vec<LoadWarning>) -> Result<T>,
+) -> Result<T> {
+    let block = load(
+        &mut &*buf,
+        *offset,
+        &LoadOptions::default(),
+        &mut Vec::new(),
+    )?;
+    *offset += buf.len() as u64;
+
+    Ok(block)
+}
+
+/// Reads bytes of a block whose items all have the same size
+async fn read_static_items_block<R: AsyncRead + Unpin>(
+    r: &mut R,
+    item_size: usize,
+) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let count = append_block_header(r, &mut buf).await?;
+
+    for _ in 0..count {
+        append_bytes(r, &mut buf, item_size).await?;
+    }
+
+    Ok(buf)
+}
+
+/// Reads block id and items count, returns the count
+async fn append_block_header<R: AsyncRead + Unpin>(r: &mut R, buf: &mut Vec<u8>) -> Result<usize> {
+    append_bytes(r, buf, size_of::<u8>() + size_of::<ReplayInt>()).await?;
+
+    let count = ReplayInt::from_le_bytes(buf[buf.len() - 4..].try_into()?);
+    if count < 0 || count as usize > DEFAULT_MAX_BLOCK_ITEMS {
+        return Err(BsorError::InvalidBsor);
+    }
+
+    Ok(count as usize)
+}
+
+/// Reads string length and the string bytes
+async fn append_string<R: AsyncRead + Unpin>(r: &mut R, buf: &mut Vec<u8>) -> Result<()> {
+    append_bytes(r, buf, size_of::<ReplayInt>()).await?;
+
+    let len = ReplayInt::from_le_bytes(buf[buf.len() - 4..].try_into()?);
+    if len < 0 || len as usize > MAX_INFO_STRING_LEN {
+        return Err(BsorError::InvalidBsor);
+    }
+
+    append_bytes(r, buf, len as usize).await
+}
+
+/// Reads exactly `len` bytes, appending them to the buffer
+async fn append_bytes<R: AsyncRead + Unpin>(
+    r: &mut R,
+    buf: &mut Vec<u8>,
+    len: usize,
+) -> Result<()> {
+    let start = buf.len();
+    buf.resize(start + len, 0);
+
+    let mut filled = start;
+    while filled < buf.len() {
+        let n = poll_fn(|cx| {
+            let mut read_buf = ReadBuf::new(&mut buf[filled..]);
+            Pin::new(&mut *r)
+                .poll_read(cx, &mut read_buf)
+                .map_ok(|_| read_buf.filled().len())
+        })
+        .await?;
+
+        if n == 0 {
+            return Err(BsorError::Io(ErrorKind::UnexpectedEof.into()));
+        }
+
+        filled += n;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{BlockType, ReplayIndex};
+    use crate::tests_util::{generate_random_replay, get_replay_buffer};
+    use std::io::Cursor;
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn it_can_load_replay_async() -> Result<()> {
+        let replay = generate_random_replay();
+        let mut cursor = Cursor::new(get_replay_buffer(&replay)?);
+
+        let result = block_on(Replay::load_async(&mut cursor))?;
+
+        assert_eq!(result.version, replay.version);
+        assert_eq!(result.info, replay.info);
+        assert_eq!(result.frames, replay.frames);
+        assert_eq!(result.notes, replay.notes);
+        assert_eq!(result.walls, replay.walls);
+        assert_eq!(result.heights, replay.heights);
+        assert_eq!(result.pauses, replay.pauses);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_stream_offset_of_unexpected_block_async() -> Result<()> {
+        let mut buf = get_replay_buffer(&generate_random_replay())?;
+        let walls_pos = ReplayIndex::index(&mut Cursor::new(&buf))?.walls.pos();
+        buf[walls_pos as usize] = BlockType::Pauses.try_into()?;
+
+        let result = block_on(Replay::load_async(&mut Cursor::new(buf)));
+
+        assert!(matches!(
+            result,
+            Err(BsorError::UnexpectedBlock { expected: 3, found: 5, offset }) if offset == walls_pos
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_error_when_async_stream_is_truncated() -> Result<()> {
+        let buf = get_replay_buffer(&generate_random_replay())?;
+        let mut cursor = Cursor::new(&buf[..buf.len() - 1]);
+
+        let result = block_on(Replay::load_async(&mut cursor));
+
+        assert!(matches!(result, Err(BsorError::Io(e)) if e.kind() == ErrorKind::UnexpectedEof));
+
+        Ok(())
+    }
+}
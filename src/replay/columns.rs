@@ -0,0 +1,115 @@
+//! struct-of-arrays layout of the replay data for analytics
+use crate::replay::note::Notes;
+use crate::replay::{LineIdx, LineLayer, ReplayFloat, ReplayInt, ReplayTime};
+
+/// Notes flattened into parallel vectors, one per field, all of them having the same length as the notes
+///
+/// Enums are stored as their `u8` values, cut info fields are `None` for notes without cut info
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ReplayColumns {
+    pub note_id: Vec<ReplayInt>,
+    pub scoring_type: Vec<u8>,
+    pub line_idx: Vec<LineIdx>,
+    pub line_layer: Vec<LineLayer>,
+    pub color_type: Vec<u8>,
+    pub cut_direction: Vec<u8>,
+    pub event_time: Vec<ReplayTime>,
+    pub spawn_time: Vec<ReplayTime>,
+    pub event_type: Vec<u8>,
+    /// Cut score of good cuts
+    pub score: Vec<Option<u32>>,
+    pub saber_speed: Vec<Option<ReplayFloat>>,
+    pub before_cut_rating: Vec<Option<ReplayFloat>>,
+    pub after_cut_rating: Vec<Option<ReplayFloat>>,
+    pub cut_distance_to_center: Vec<Option<ReplayFloat>>,
+    pub time_deviation: Vec<Option<ReplayFloat>>,
+}
+
+impl ReplayColumns {
+    /// Flattens notes into columns
+    pub fn from_notes(notes: &Notes) -> ReplayColumns {
+        let mut columns = ReplayColumns::default();
+
+        for note in notes.iter() {
+            let cut_info = note.cut_info.as_ref();
+
+            columns.note_id.push(note.id());
+            columns.scoring_type.push(note.scoring_type as u8);
+            columns.line_idx.push(note.line_idx);
+            columns.line_layer.push(note.line_layer);
+            columns.color_type.push(note.color_type as u8);
+            columns.cut_direction.push(note.cut_direction as u8);
+            columns.event_time.push(note.event_time);
+            columns.spawn_time.push(note.spawn_time);
+            columns
+                .event_type
+                .push(note.event_type.try_into().unwrap_or(u8::MAX));
+            columns.score.push(note.cut_score());
+            columns.saber_speed.push(cut_info.map(|c| c.saber_speed));
+            columns
+                .before_cut_rating
+                .push(cut_info.map(|c| c.before_cut_rating));
+            columns
+                .after_cut_rating
+                .push(cut_info.map(|c| c.after_cut_rating));
+            columns
+                .cut_distance_to_center
+                .push(cut_info.map(|c| c.cut_distance_to_center));
+            columns
+                .time_deviation
+                .push(cut_info.map(|c| c.time_deviation));
+        }
+
+        columns
+    }
+
+    /// Returns number of rows
+    pub fn len(&self) -> usize {
+        self.note_id.len()
+    }
+
+    /// Returns whether there are no rows
+    pub fn is_empty(&self) -> bool {
+        self.note_id.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::note::NoteEventType;
+    use crate::tests_util::generate_random_note;
+
+    #[test]
+    fn it_can_flatten_notes_into_columns() {
+        let notes = Notes::new(Vec::from([
+            generate_random_note(NoteEventType::Good),
+            generate_random_note(NoteEventType::Miss),
+            generate_random_note(NoteEventType::Bomb),
+        ]));
+
+        let columns = ReplayColumns::from_notes(&notes);
+
+        assert_eq!(columns.len(), notes.len());
+        assert_eq!(columns.event_time.len(), notes.len());
+        assert_eq!(columns.score.len(), notes.len());
+        assert_eq!(columns.time_deviation.len(), notes.len());
+
+        for (idx, note) in notes.iter().enumerate() {
+            assert_eq!(columns.note_id[idx], note.id());
+            assert_eq!(columns.color_type[idx], note.color_type as u8);
+            assert_eq!(
+                columns.event_type[idx] as ReplayInt,
+                ReplayInt::from(note.event_type)
+            );
+            assert_eq!(columns.event_time[idx], note.event_time);
+            assert_eq!(columns.spawn_time[idx], note.spawn_time);
+            assert_eq!(
+                columns.saber_speed[idx],
+                note.cut_info.as_ref().map(|c| c.saber_speed)
+            );
+        }
+        assert!(columns.score[0].is_some());
+        assert_eq!(columns.score[1], None);
+    }
+}
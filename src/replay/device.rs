@@ -0,0 +1,168 @@
+//! enums standardizing HMD and controller names stored in the Info block
+use std::fmt;
+
+/// Head-mounted display kind parsed from [crate::replay::info::Info::hmd]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Hmd {
+    Rift,
+    RiftS,
+    Quest,
+    Quest2,
+    Quest3,
+    QuestPro,
+    Vive,
+    VivePro,
+    ViveCosmos,
+    Index,
+    WindowsMr,
+    PicoNeo3,
+    Pico4,
+    /// Any unrecognized HMD, contains the original string
+    Unknown(String),
+}
+
+impl Hmd {
+    /// Parses device string, ignoring case, spaces and punctuation (so `Quest_2`, `quest 2` and `Quest2` are all [Hmd::Quest2])
+    pub fn parse(s: &str) -> Hmd {
+        let name = normalize(s);
+
+        // more specific names have to be checked first
+        let known = [
+            ("questpro", Hmd::QuestPro),
+            ("quest3", Hmd::Quest3),
+            ("quest2", Hmd::Quest2),
+            ("quest", Hmd::Quest),
+            ("rifts", Hmd::RiftS),
+            ("rift", Hmd::Rift),
+            ("vivepro", Hmd::VivePro),
+            ("vivecosmos", Hmd::ViveCosmos),
+            ("vive", Hmd::Vive),
+            ("index", Hmd::Index),
+            ("windowsmr", Hmd::WindowsMr),
+            ("wmr", Hmd::WindowsMr),
+            ("reverb", Hmd::WindowsMr),
+            ("piconeo3", Hmd::PicoNeo3),
+            ("pico4", Hmd::Pico4),
+        ];
+
+        find_known(&name, known).unwrap_or_else(|| Hmd::Unknown(s.to_owned()))
+    }
+}
+
+impl fmt::Display for Hmd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Hmd::Unknown(s) => write!(f, "{}", s),
+            hmd => write!(f, "{:?}", hmd),
+        }
+    }
+}
+
+/// Controller kind parsed from [crate::replay::info::Info::controller]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Controller {
+    OculusTouch,
+    Knuckles,
+    ViveWand,
+    ViveCosmos,
+    WindowsMr,
+    Pico,
+    /// Any unrecognized controller, contains the original string
+    Unknown(String),
+}
+
+impl Controller {
+    /// Parses device string, ignoring case, spaces and punctuation
+    pub fn parse(s: &str) -> Controller {
+        let name = normalize(s);
+
+        // more specific names have to be checked first
+        let known = [
+            ("touch", Controller::OculusTouch),
+            ("oculus", Controller::OculusTouch),
+            ("quest", Controller::OculusTouch),
+            ("knuckles", Controller::Knuckles),
+            ("index", Controller::Knuckles),
+            ("vivecosmos", Controller::ViveCosmos),
+            ("vive", Controller::ViveWand),
+            ("windowsmr", Controller::WindowsMr),
+            ("wmr", Controller::WindowsMr),
+            ("hpmotion", Controller::WindowsMr),
+            ("pico", Controller::Pico),
+        ];
+
+        find_known(&name, known).unwrap_or_else(|| Controller::Unknown(s.to_owned()))
+    }
+}
+
+impl fmt::Display for Controller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Controller::Unknown(s) => write!(f, "{}", s),
+            controller => write!(f, "{:?}", controller),
+        }
+    }
+}
+
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn find_known<T, const N: usize>(name: &str, known: [(&str, T); N]) -> Option<T> {
+    known
+        .into_iter()
+        .find(|(pattern, _)| name.contains(pattern))
+        .map(|(_, kind)| kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_can_parse_hmd() {
+        assert_eq!(Hmd::parse("Rift_S"), Hmd::RiftS);
+        assert_eq!(Hmd::parse("Oculus Rift CV1"), Hmd::Rift);
+        assert_eq!(Hmd::parse("Quest_2"), Hmd::Quest2);
+        assert_eq!(Hmd::parse("quest 3"), Hmd::Quest3);
+        assert_eq!(Hmd::parse("Meta Quest Pro"), Hmd::QuestPro);
+        assert_eq!(Hmd::parse("Index"), Hmd::Index);
+        assert_eq!(Hmd::parse("Vive Pro 2"), Hmd::VivePro);
+        assert_eq!(Hmd::parse("HP Reverb G2"), Hmd::WindowsMr);
+        assert_eq!(
+            Hmd::parse("Bigscreen Beyond"),
+            Hmd::Unknown("Bigscreen Beyond".to_owned())
+        );
+    }
+
+    #[test]
+    fn it_can_parse_controller() {
+        assert_eq!(
+            Controller::parse("Oculus Touch Controller"),
+            Controller::OculusTouch
+        );
+        assert_eq!(Controller::parse("Knuckles EV3"), Controller::Knuckles);
+        assert_eq!(
+            Controller::parse("Vive Controller MV"),
+            Controller::ViveWand
+        );
+        assert_eq!(
+            Controller::parse("WindowsMR: 0x045E/0x065B"),
+            Controller::WindowsMr
+        );
+        assert_eq!(
+            Controller::parse("unknown"),
+            Controller::Unknown("unknown".to_owned())
+        );
+    }
+
+    #[test]
+    fn it_can_display_device_kinds() {
+        assert_eq!(Hmd::Quest2.to_string(), "Quest2");
+        assert_eq!(Hmd::Unknown("Foo".to_owned()).to_string(), "Foo");
+        assert_eq!(Controller::Knuckles.to_string(), "Knuckles");
+    }
+}
@@ -1,20 +1,32 @@
 //! errors used in crate
+use crate::replay::BlockType;
 use std::array::TryFromSliceError;
 use std::num::ParseIntError;
 use std::str::Utf8Error;
 use std::{error, fmt, io};
 
 /// All possible error variants when parsing a BSOR replay
+///
+/// More variants may be added in minor versions, so matching on it needs a wildcard arm
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum BsorError {
     /// Invalid BSOR, i.e. the magic variable is invalid, or there was an error in the structure of the BSOR
     InvalidBsor,
     /// BSOR version is unsupported. Enum value contains BSOR version
     UnsupportedVersion(u8),
+    /// Block id differs from the one expected at given position. Contains expected and found block ids and the stream offset of the block id
+    UnexpectedBlock {
+        expected: u8,
+        found: u8,
+        offset: u64,
+    },
+    /// Replay written and loaded back differs from the original one, see [crate::replay::Replay::assert_roundtrip()]. Contains the first block that differs
+    RoundTripMismatch(BlockType),
     /// IO error. Enum value contains concrete [io::Error]
     Io(io::Error),
     /// Decoding error
-    Decoding(Box<dyn error::Error>),
+    Decoding(Box<dyn error::Error + Send + Sync>),
 }
 
 impl fmt::Display for BsorError {
@@ -22,12 +34,33 @@ impl fmt::Display for BsorError {
         match self {
             BsorError::InvalidBsor => write!(f, "invalid bsor"),
             BsorError::UnsupportedVersion(v) => write!(f, "invalid bsor version ({})", v),
+            BsorError::UnexpectedBlock {
+                expected,
+                found,
+                offset,
+            } => {
+                write!(f, "unexpected block: expected ")?;
+                write_block_name(f, *expected)?;
+                write!(f, ", found ")?;
+                write_block_name(f, *found)?;
+                write!(f, " at offset {}", offset)
+            }
+            BsorError::RoundTripMismatch(bt) => {
+                write!(f, "round trip mismatch in {} block", bt.name())
+            }
             BsorError::Io(e) => write!(f, "io error: {}", e),
             BsorError::Decoding(e) => write!(f, "decoding error: {}", e),
         }
     }
 }
 
+fn write_block_name(f: &mut fmt::Formatter<'_>, id: u8) -> fmt::Result {
+    match BlockType::from_id(id) {
+        Some(bt) => write!(f, "{} block", bt.name()),
+        None => write!(f, "unknown block id {}", id),
+    }
+}
+
 impl From<io::Error> for BsorError {
     fn from(error: io::Error) -> Self {
         BsorError::Io(error)
@@ -52,11 +85,20 @@ impl From<TryFromSliceError> for BsorError {
     }
 }
 
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for BsorError {
+    fn from(error: serde_json::Error) -> Self {
+        BsorError::Decoding(Box::new(error))
+    }
+}
+
 impl error::Error for BsorError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match &self {
             BsorError::InvalidBsor => None,
             BsorError::UnsupportedVersion(_) => None,
+            BsorError::UnexpectedBlock { .. } => None,
+            BsorError::RoundTripMismatch(_) => None,
             BsorError::Io(e) => Some(e),
             BsorError::Decoding(e) => {
                 if let Some(err) = e.downcast_ref::<ParseIntError>() {
@@ -67,7 +109,12 @@ impl error::Error for BsorError {
                     return Some(err);
                 }
 
-                return None;
+                #[cfg(feature = "serde")]
+                if let Some(err) = e.downcast_ref::<serde_json::Error>() {
+                    return Some(err);
+                }
+
+                None
             }
         }
     }
@@ -78,6 +125,13 @@ mod tests {
     use super::*;
     use std::error::Error;
 
+    #[test]
+    fn it_can_be_sent_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<BsorError>();
+    }
+
     #[test]
     fn it_can_convert_io_error_to_bsor_error() {
         let io_err = io::Error::new(io::ErrorKind::UnexpectedEof, "Test error");
@@ -132,6 +186,29 @@ mod tests {
         let err: Box<dyn Error> = Box::new(BsorError::UnsupportedVersion(1));
         assert_eq!(format!("{}", err), "invalid bsor version (1)");
 
+        let err: Box<dyn Error> = Box::new(BsorError::UnexpectedBlock {
+            expected: 1,
+            found: 2,
+            offset: 42,
+        });
+        assert_eq!(
+            format!("{}", err),
+            "unexpected block: expected Frames block, found Notes block at offset 42"
+        );
+
+        let err: Box<dyn Error> = Box::new(BsorError::UnexpectedBlock {
+            expected: 3,
+            found: 255,
+            offset: 0,
+        });
+        assert_eq!(
+            format!("{}", err),
+            "unexpected block: expected Walls block, found unknown block id 255 at offset 0"
+        );
+
+        let err: Box<dyn Error> = Box::new(BsorError::RoundTripMismatch(BlockType::Notes));
+        assert_eq!(format!("{}", err), "round trip mismatch in Notes block");
+
         let err: Box<dyn Error> = Box::new(BsorError::Io(io::Error::new(
             io::ErrorKind::UnexpectedEof,
             "Test error",
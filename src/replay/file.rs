@@ -0,0 +1,106 @@
+//! A module for working with an indexed replay file, loading its blocks on demand
+//!
+//! # Example
+//! ```no_run
+//! use bsor::replay::file::ReplayFile;
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! let mut replay_file = ReplayFile::open(BufReader::new(File::open("example.bsor").unwrap())).unwrap();
+//!
+//! let notes = replay_file.notes().unwrap();
+//! let middle_frame = replay_file.frame(replay_file.index().frames.len() as usize / 2).unwrap();
+//! println!("{} notes, middle frame: {:#?}", notes.len(), middle_frame);
+//! ```
+use super::frame::{Frame, Frames};
+use super::height::Heights;
+use super::info::Info;
+use super::note::Notes;
+use super::pause::Pauses;
+use super::wall::Walls;
+use super::{LoadBlock, ReplayIndex, Result};
+use std::io::{Read, Seek};
+
+/// Indexed replay owning its reader, so blocks can be loaded on demand without passing the reader around
+pub struct ReplayFile<RS: Read + Seek> {
+    r: RS,
+    index: ReplayIndex,
+}
+
+impl<RS: Read + Seek> ReplayFile<RS> {
+    /// Indexes the replay, see [ReplayIndex::index()]
+    pub fn open(mut r: RS) -> Result<Self> {
+        let index = ReplayIndex::index(&mut r)?;
+
+        Ok(Self { r, index })
+    }
+
+    /// Returns the replay index
+    pub fn index(&self) -> &ReplayIndex {
+        &self.index
+    }
+
+    /// Returns Info block, loaded while indexing
+    pub fn info(&self) -> &Info {
+        &self.index.info
+    }
+
+    /// Loads Frames block
+    pub fn frames(&mut self) -> Result<Frames> {
+        self.index.frames.load(&mut self.r)
+    }
+
+    /// Loads a single frame, or returns `None` if `idx` is out of range
+    pub fn frame(&mut self, idx: usize) -> Result<Option<Frame>> {
+        self.index.frames.load_frame(&mut self.r, idx)
+    }
+
+    /// Loads Notes block
+    pub fn notes(&mut self) -> Result<Notes> {
+        self.index.notes.load(&mut self.r)
+    }
+
+    /// Loads Walls block
+    pub fn walls(&mut self) -> Result<Walls> {
+        self.index.walls.load(&mut self.r)
+    }
+
+    /// Loads Heights block
+    pub fn heights(&mut self) -> Result<Heights> {
+        self.index.heights.load(&mut self.r)
+    }
+
+    /// Loads Pauses block
+    pub fn pauses(&mut self) -> Result<Pauses> {
+        self.index.pauses.load(&mut self.r)
+    }
+
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> RS {
+        self.r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_util::{generate_random_replay, get_replay_buffer};
+    use std::io::Cursor;
+
+    #[test]
+    fn it_can_load_blocks_on_demand() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let mut replay_file = ReplayFile::open(Cursor::new(get_replay_buffer(&replay)?))?;
+
+        assert_eq!(*replay_file.info(), replay.info);
+        assert_eq!(replay_file.notes()?, replay.notes);
+        assert_eq!(replay_file.frame(1)?, Some(replay.frames[1].clone()));
+        assert_eq!(replay_file.frame(0)?, Some(replay.frames[0].clone()));
+        assert_eq!(replay_file.frame(replay.frames.len())?, None);
+        assert_eq!(replay_file.pauses()?, replay.pauses);
+        assert_eq!(replay_file.frames()?, replay.frames);
+
+        Ok(())
+    }
+}
@@ -1,34 +1,249 @@
 //! structs storing the Frames block data
-use super::{read_utils, vector, ReplayInt, ReplayTime, Result};
+use super::{read_utils, vector, write_utils, ReplayFloat, ReplayInt, ReplayTime, Result};
+use crate::replay::time::SongTime;
 use crate::replay::{
-    assert_start_of_block, BlockIndex, BlockType, GetStaticBlockSize, LoadBlock, LoadRealBlockSize,
+    expect_block, load_block_items, write_block_items, ApproxEq, BlockIndex, BlockType, BsorError,
+    GetStaticBlockSize, LoadBlock, LoadOptions, LoadRealBlockSize, LoadWarning,
 };
-use std::io::{Read, Seek, SeekFrom};
+use std::f32::consts::{PI, TAU};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::marker::PhantomData;
 use std::mem::size_of;
 use std::ops::Deref;
 
+/// Min FPS drop below both neighbouring frames for a frame to be reported by [Frames::fps_dips()]
+pub const FPS_DIP_MARGIN: ReplayInt = 10;
+
 /// Struct implements [std::ops::Deref] trait so it could be treated as Vec<[Frame]>
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Frames(Vec<Frame>);
 
 impl Frames {
-    #[cfg(test)]
-    pub(crate) fn new(vec: Vec<Frame>) -> Frames {
+    /// Creates block from given items
+    pub fn new(vec: Vec<Frame>) -> Frames {
         Frames(vec)
     }
 
-    pub(crate) fn load<R: Read>(r: &mut R) -> Result<Frames> {
-        assert_start_of_block(r, BlockType::Frames)?;
+    pub(crate) fn load<RS: Read + Seek>(r: &mut RS) -> Result<Frames> {
+        let offset = r.stream_position()?;
+
+        Self::load_with_options(r, offset, &LoadOptions::default(), &mut Vec::new())
+    }
+
+    pub(crate) fn load_with_options<R: Read>(
+        r: &mut R,
+        offset: u64,
+        options: &LoadOptions,
+        warnings: &mut Vec<LoadWarning>,
+    ) -> Result<Frames> {
+        Ok(Frames(load_block_items(
+            r,
+            BlockType::Frames,
+            offset,
+            options,
+            warnings,
+            Frame::load,
+        )?))
+    }
+
+    /// Returns the last frame rendered at or before given song time, or `None` if the time precedes the first frame
+    pub fn frame_at_time(&self, time: SongTime) -> Option<&Frame> {
+        let idx = self.partition_point(|f| f.time <= time.0);
+
+        idx.checked_sub(1).map(|idx| &self[idx])
+    }
+
+    /// Returns the pose at given song time, interpolated between the frames around it, or `None` if the time is outside of the captured range
+    ///
+    /// Positions are interpolated linearly and rotations spherically, FPS is taken from the preceding frame
+    pub fn pose_at(&self, time: ReplayTime) -> Option<Frame> {
+        let idx = self.partition_point(|f| f.time <= time);
+
+        let before = &self[idx.checked_sub(1)?];
+        let Some(after) = self.get(idx) else {
+            return (before.time == time).then(|| before.clone());
+        };
+
+        let t = (time - before.time) / (after.time - before.time);
+
+        Some(Frame {
+            time,
+            fps: before.fps,
+            head: before.head.interpolate(&after.head, t),
+            left_hand: before.left_hand.interpolate(&after.left_hand, t),
+            right_hand: before.right_hand.interpolate(&after.right_hand, t),
+        })
+    }
 
-        let count = read_utils::read_int(r)? as usize;
-        let mut vec = Vec::<Frame>::with_capacity(count);
+    /// Returns the frame closest to given song time, or `None` if there are no frames
+    pub fn nearest_frame(&self, time: SongTime) -> Option<&Frame> {
+        let idx = self.partition_point(|f| f.time <= time.0);
 
-        for _ in 0..count {
-            vec.push(Frame::load(r)?);
+        let before = idx.checked_sub(1).map(|idx| &self[idx]);
+        let after = self.get(idx);
+
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                if time.0 - before.time <= after.time - time.0 {
+                    Some(before)
+                } else {
+                    Some(after)
+                }
+            }
+            (before, after) => before.or(after),
+        }
+    }
+
+    /// Returns total yaw change of the head rotation in radians, i.e. how much the player physically turned (positive values are clockwise turns when looking from above)
+    ///
+    /// Yaw changes between consecutive frames are unwrapped into (-π, π], so a full turn adds up to 2π instead of wrapping around
+    pub fn total_head_yaw(&self) -> ReplayFloat {
+        self.windows(2)
+            .map(|w| {
+                let delta =
+                    w[1].head.rotation.to_euler_angles().1 - w[0].head.rotation.to_euler_angles().1;
+
+                (delta + PI).rem_euclid(TAU) - PI
+            })
+            .sum()
+    }
+
+    /// Returns indices of frames whose FPS is a local minimum, lower than FPS of both neighbouring frames by more than [FPS_DIP_MARGIN]
+    pub fn fps_dips(&self) -> Vec<usize> {
+        self.windows(3)
+            .enumerate()
+            .filter(|(_, w)| {
+                w[1].fps < w[0].fps.saturating_sub(FPS_DIP_MARGIN)
+                    && w[1].fps < w[2].fps.saturating_sub(FPS_DIP_MARGIN)
+            })
+            .map(|(idx, _)| idx + 1)
+            .collect()
+    }
+
+    /// Returns whether frames were recorded at a fixed timestep, i.e. time delta between any two consecutive frames differs from the mean delta by at most `eps`
+    ///
+    /// Less than 3 frames are always considered fixed timestep
+    pub fn is_fixed_timestep(&self, eps: ReplayTime) -> bool {
+        if self.len() < 3 {
+            return true;
         }
 
-        Ok(Frames(vec))
+        let mean_delta =
+            (self[self.len() - 1].time - self[0].time) / (self.len() - 1) as ReplayTime;
+
+        self.windows(2)
+            .all(|w| ((w[1].time - w[0].time) - mean_delta).abs() <= eps)
+    }
+
+    /// Returns movement efficiency of the left and right hand respectively, i.e. total path length divided by the net displacement between the first and the last frame
+    ///
+    /// Higher value means more wasted motion. Returns 0 for a hand whose net displacement is 0 (including less than 2 frames)
+    pub fn hand_efficiency(&self) -> (f32, f32) {
+        let efficiency = |hand: fn(&Frame) -> &PositionAndRotation| {
+            let (Some(first), Some(last)) = (self.first(), self.last()) else {
+                return 0.0;
+            };
+
+            let displacement = hand(first).position.distance(&hand(last).position);
+            if displacement == 0.0 {
+                return 0.0;
+            }
+
+            self.path_length(hand) / displacement
+        };
+
+        (efficiency(|f| &f.left_hand), efficiency(|f| &f.right_hand))
+    }
+
+    fn path_length(&self, hand: fn(&Frame) -> &PositionAndRotation) -> ReplayFloat {
+        self.windows(2)
+            .map(|w| hand(&w[0]).position.distance(&hand(&w[1]).position))
+            .sum()
+    }
+
+    /// Returns the distance between left and right hand positions for each frame
+    pub fn hand_separation(&self) -> Vec<(ReplayTime, ReplayFloat)> {
+        self.iter()
+            .map(|f| {
+                (
+                    f.time,
+                    f.left_hand.position.distance(&f.right_hand.position),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns times of frames in which the hands crossed, i.e. the left hand x-coordinate moved to the other side of the right hand one
+    ///
+    /// Frames with both hands at the same x-coordinate do not count as a crossing by themselves, the side is compared with the last frame in which hands were apart
+    pub fn crossovers(&self) -> Vec<ReplayTime> {
+        let mut crossovers = Vec::new();
+        let mut last_side = None;
+
+        for frame in self.iter() {
+            let diff = frame.right_hand.position.x - frame.left_hand.position.x;
+            if diff == 0.0 || diff.is_nan() {
+                continue;
+            }
+
+            let side = diff > 0.0;
+            if last_side.is_some_and(|last| last != side) {
+                crossovers.push(frame.time);
+            }
+            last_side = Some(side);
+        }
+
+        crossovers
+    }
+
+    /// Estimates how many times per second tracking data is actually updated, as opposed to render FPS stored in [Frame::fps]
+    ///
+    /// Counts frames in which the position of the head or any hand differs from the previous frame and divides it by the time covered by the frames. A 90Hz tracker rendered at 144fps repeats positions in some frames, so the estimate stays close to 90 while the FPS is 144. Returns 0 if there are less than 2 frames or they do not span any time
+    pub fn tracking_rate_estimate(&self) -> f32 {
+        let (Some(first), Some(last)) = (self.first(), self.last()) else {
+            return 0.0;
+        };
+
+        let duration = last.time - first.time;
+        if duration <= 0.0 {
+            return 0.0;
+        }
+
+        let updates = self
+            .windows(2)
+            .filter(|w| {
+                w[0].head.position != w[1].head.position
+                    || w[0].left_hand.position != w[1].left_hand.position
+                    || w[0].right_hand.position != w[1].right_hand.position
+            })
+            .count();
+
+        updates as f32 / duration
+    }
+
+    /// Returns min, max, mean and median of render FPS stored in [Frame::fps], or None if there are no frames
+    pub fn fps_stats(&self) -> Option<FpsStats> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut fps = self.iter().map(|f| f.fps).collect::<Vec<_>>();
+        fps.sort_unstable();
+
+        let len = fps.len();
+        let median = if len % 2 == 0 {
+            (fps[len / 2 - 1] as f32 + fps[len / 2] as f32) / 2.0
+        } else {
+            fps[len / 2] as f32
+        };
+
+        Some(FpsStats {
+            min: fps[0],
+            max: fps[len - 1],
+            mean: fps.iter().map(|f| *f as f32).sum::<f32>() / len as f32,
+            median,
+        })
     }
 
     pub(crate) fn load_block<RS: Read + Seek>(
@@ -39,6 +254,45 @@ impl Frames {
 
         Self::load(r)
     }
+
+    /// Writes frames as CSV with a header row, one row per frame with its time, fps and the position (x, y, z) and rotation (x, y, z, w) of the head, left and right hand
+    pub fn to_csv<W: Write>(&self, w: &mut W) -> Result<()> {
+        let mut header = vec!["time".to_owned(), "fps".to_owned()];
+        for part in ["head", "left_hand", "right_hand"] {
+            header.extend(["x", "y", "z"].map(|c| format!("{}_position_{}", part, c)));
+            header.extend(["x", "y", "z", "w"].map(|c| format!("{}_rotation_{}", part, c)));
+        }
+        writeln!(w, "{}", header.join(","))?;
+
+        for frame in self.iter() {
+            write!(w, "{},{}", frame.time, frame.fps)?;
+
+            for PositionAndRotation { position, rotation } in
+                [&frame.head, &frame.left_hand, &frame.right_hand]
+            {
+                write!(
+                    w,
+                    ",{},{},{},{},{},{},{}",
+                    position.x,
+                    position.y,
+                    position.z,
+                    rotation.x,
+                    rotation.y,
+                    rotation.z,
+                    rotation.w
+                )?;
+            }
+
+            writeln!(w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the block id, items count and all items
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_block_items(w, BlockType::Frames, &self.0, Frame::write)
+    }
 }
 
 impl Deref for Frames {
@@ -64,11 +318,105 @@ impl LoadBlock for BlockIndex<Frames> {
     }
 }
 
+impl_block!(Frames, Frame, [time]);
+
+impl BlockIndex<Frames> {
+    /// Returns iterator reading indexed frames one at a time, see [FrameIterator]
+    pub fn iter<'a, RS: Read + Seek>(&self, r: &'a mut RS) -> Result<FrameIterator<&'a mut RS>> {
+        r.seek(SeekFrom::Start(self.pos))?;
+
+        FrameIterator::with_offset(r, self.pos)
+    }
+
+    /// Loads a single indexed frame, or returns `None` if `idx` is out of range
+    pub fn load_frame<RS: Read + Seek>(&self, r: &mut RS, idx: usize) -> Result<Option<Frame>> {
+        if idx >= self.len().max(0) as usize {
+            return Ok(None);
+        }
+
+        r.seek(SeekFrom::Start(
+            self.pos + Frames::get_static_size() as u64 + (idx * FRAME_SIZE) as u64,
+        ))?;
+
+        Ok(Some(Frame::load(r)?))
+    }
+}
+
+/// Render FPS statistics of frames, see [Frames::fps_stats()]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FpsStats {
+    pub min: ReplayInt,
+    pub max: ReplayInt,
+    pub mean: f32,
+    pub median: f32,
+}
+
+/// Size in bytes of a single frame as stored in the replay file
+const FRAME_SIZE: usize =
+    size_of::<ReplayTime>() + size_of::<ReplayInt>() + (size_of::<ReplayFloat>() * 7) * 3;
+
+/// Iterator reading frames one at a time, without loading the whole Frames block into memory
+///
+/// Stops after the first error
+pub struct FrameIterator<R: Read> {
+    r: R,
+    remaining: usize,
+    buf: [u8; FRAME_SIZE],
+}
+
+impl<R: Read> FrameIterator<R> {
+    /// Creates iterator from a reader positioned at the start of the Frames block, block id and frames count are read immediately
+    ///
+    /// Offsets in errors are counted from the reader position at the time of the call
+    pub fn new(r: R) -> Result<Self> {
+        Self::with_offset(r, 0)
+    }
+
+    /// Same as [FrameIterator::new()], with errors reporting given stream offset of the block
+    pub(crate) fn with_offset(mut r: R, offset: u64) -> Result<Self> {
+        expect_block(&mut r, BlockType::Frames, offset)?;
+
+        let count = read_utils::read_int(&mut r)?;
+        if count < 0 {
+            return Err(BsorError::InvalidBsor);
+        }
+
+        Ok(Self {
+            r,
+            remaining: count as usize,
+            buf: [0; FRAME_SIZE],
+        })
+    }
+}
+
+impl<R: Read> Iterator for FrameIterator<R> {
+    type Item = Result<Frame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let result = read_utils::read_into_buffer(&mut self.r, &mut self.buf)
+            .and_then(|_| Frame::load(&mut self.buf.as_slice()));
+        if result.is_err() {
+            self.remaining = 0;
+        }
+
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
 impl LoadRealBlockSize for Frames {
     type Item = Frames;
 
     fn load_real_block_size<RS: Read + Seek>(r: &mut RS, pos: u64) -> Result<BlockIndex<Frames>> {
-        assert_start_of_block(r, BlockType::Frames)?;
+        expect_block(r, BlockType::Frames, pos)?;
 
         let count = read_utils::read_int(r)?;
 
@@ -82,9 +430,16 @@ impl LoadRealBlockSize for Frames {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Frame {
+    /// song time of the frame, in seconds
     pub time: ReplayTime,
+    /// render FPS
     pub fps: ReplayInt,
     pub head: PositionAndRotation,
     pub left_hand: PositionAndRotation,
@@ -107,6 +462,14 @@ impl Frame {
             right_hand,
         })
     }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_utils::write_float(w, self.time)?;
+        write_utils::write_int(w, self.fps)?;
+        self.head.write(w)?;
+        self.left_hand.write(w)?;
+        self.right_hand.write(w)
+    }
 }
 
 impl GetStaticBlockSize for Frame {
@@ -117,7 +480,22 @@ impl GetStaticBlockSize for Frame {
     }
 }
 
-#[derive(PartialEq, Debug)]
+impl ApproxEq for Frame {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.time.approx_eq(&other.time)
+            && self.fps == other.fps
+            && self.head.approx_eq(&other.head)
+            && self.left_hand.approx_eq(&other.left_hand)
+            && self.right_hand.approx_eq(&other.right_hand)
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PositionAndRotation {
     pub position: vector::Vector3,
     pub rotation: vector::Vector4,
@@ -130,6 +508,18 @@ impl PositionAndRotation {
 
         Ok(Self { position, rotation })
     }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        self.position.write(w)?;
+        self.rotation.write(w)
+    }
+
+    fn interpolate(&self, other: &PositionAndRotation, t: ReplayFloat) -> PositionAndRotation {
+        PositionAndRotation {
+            position: self.position.lerp(&other.position, t),
+            rotation: self.rotation.slerp(&other.rotation, t),
+        }
+    }
 }
 
 impl GetStaticBlockSize for PositionAndRotation {
@@ -138,16 +528,58 @@ impl GetStaticBlockSize for PositionAndRotation {
     }
 }
 
+impl ApproxEq for PositionAndRotation {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.position.approx_eq(&other.position) && self.rotation.approx_eq(&other.rotation)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::replay::BsorError;
-    use crate::tests_util::{append_frame, generate_random_frame, get_frames_buffer};
+    use crate::replay::vector::{Vector3, Vector4};
+    use crate::replay::ReplayIndex;
+    use crate::tests_util::{
+        append_frame, generate_random_frame, generate_random_replay, get_frames_buffer,
+        get_replay_buffer,
+    };
     use std::io::Cursor;
 
+    #[test]
+    fn it_iterates_frames_in_order() {
+        let items = vec![
+            generate_random_frame(),
+            generate_random_frame(),
+            generate_random_frame(),
+        ];
+        let frames = Frames(items.clone());
+
+        let mut borrowed = Vec::new();
+        for item in &frames {
+            borrowed.push(item.clone());
+        }
+        assert_eq!(borrowed, items);
+        assert!(frames.iter().eq(items.iter()));
+
+        assert_eq!(frames.into_iter().collect::<Vec<_>>(), items);
+    }
+
+    #[test]
+    fn it_can_access_frames_by_index_and_get_vec() {
+        let items = vec![generate_random_frame(), generate_random_frame()];
+        let frames = Frames(items.clone());
+
+        assert_eq!(frames.len(), 2);
+        assert!(!frames.is_empty());
+        assert_eq!(frames[1], items[1]);
+        assert_eq!(frames.get_vec(), &items);
+        assert!(Frames(Vec::new()).get_vec().is_empty());
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_frame() {
         assert_eq!(Frame::get_static_size(), 92);
+        assert_eq!(FRAME_SIZE, Frame::get_static_size());
     }
 
     #[test]
@@ -163,7 +595,7 @@ mod tests {
     }
 
     #[test]
-    fn it_returns_invalid_bsor_error_when_frames_block_id_is_invalid() -> Result<()> {
+    fn it_returns_unexpected_block_error_when_frames_block_id_is_invalid() -> Result<()> {
         let frames = Vec::from([generate_random_frame(), generate_random_frame()]);
 
         let mut buf = get_frames_buffer(&frames)?;
@@ -171,7 +603,10 @@ mod tests {
 
         let result = Frames::load(&mut Cursor::new(buf));
 
-        assert!(matches!(result, Err(BsorError::InvalidBsor)));
+        assert!(matches!(
+            result,
+            Err(BsorError::UnexpectedBlock { found: 255, .. })
+        ));
 
         Ok(())
     }
@@ -190,6 +625,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn it_can_write_frames() -> Result<()> {
+        let frames = Vec::from([generate_random_frame(), generate_random_frame()]);
+
+        let mut buf = Vec::new();
+        Frames::new(frames.clone()).write(&mut buf)?;
+
+        assert_eq!(buf, get_frames_buffer(&frames)?);
+
+        Ok(())
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_frames() {
         assert_eq!(Frames::get_static_size(), 5);
@@ -217,4 +664,409 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn it_can_estimate_tracking_rate() {
+        let mut previous = generate_random_frame();
+        let frames = (0..=100)
+            .map(|i| {
+                let mut frame = if i % 2 == 0 {
+                    generate_random_frame()
+                } else {
+                    previous.clone()
+                };
+                frame.time = i as ReplayTime * 0.01;
+                frame.fps = 100;
+                previous = frame.clone();
+
+                frame
+            })
+            .collect::<Vec<_>>();
+
+        let rate = Frames::new(frames).tracking_rate_estimate();
+
+        assert!((rate - 50.0).abs() < 0.01, "rate: {}", rate);
+        assert_eq!(Frames::new(Vec::new()).tracking_rate_estimate(), 0.0);
+    }
+
+    #[test]
+    fn it_can_find_frame_at_time() {
+        let frames = Frames::new(
+            (0..3)
+                .map(|i| {
+                    let mut frame = generate_random_frame();
+                    frame.time = i as ReplayTime;
+
+                    frame
+                })
+                .collect(),
+        );
+
+        assert_eq!(frames.frame_at_time(SongTime(-0.5)), None);
+        assert_eq!(frames.frame_at_time(SongTime(0.0)), Some(&frames[0]));
+        assert_eq!(frames.frame_at_time(SongTime(1.5)), Some(&frames[1]));
+        assert_eq!(frames.frame_at_time(5.0.into()), Some(&frames[2]));
+    }
+
+    #[test]
+    fn it_can_find_nearest_frame() {
+        let frames = Frames::new(
+            (0..3)
+                .map(|i| {
+                    let mut frame = generate_random_frame();
+                    frame.time = i as ReplayTime;
+
+                    frame
+                })
+                .collect(),
+        );
+
+        assert_eq!(frames.nearest_frame(SongTime(-0.5)), Some(&frames[0]));
+        assert_eq!(frames.nearest_frame(SongTime(1.4)), Some(&frames[1]));
+        assert_eq!(frames.nearest_frame(SongTime(1.6)), Some(&frames[2]));
+        assert_eq!(frames.nearest_frame(SongTime(5.0)), Some(&frames[2]));
+        assert_eq!(Frames::new(Vec::new()).nearest_frame(SongTime(1.0)), None);
+    }
+
+    #[test]
+    fn it_can_compute_total_head_yaw() {
+        let step = 10.0f32.to_radians();
+        let frames = Frames::new(
+            (0..=45)
+                .map(|i| {
+                    let half = i as ReplayFloat * step / 2.0;
+                    let mut frame = generate_random_frame();
+                    frame.head.rotation = vector::Vector4 {
+                        x: 0.0,
+                        y: half.sin(),
+                        z: 0.0,
+                        w: half.cos(),
+                    };
+
+                    frame
+                })
+                .collect(),
+        );
+
+        assert!((frames.total_head_yaw() - 450.0f32.to_radians()).abs() < 1e-3);
+        assert_eq!(Frames::new(Vec::new()).total_head_yaw(), 0.0);
+    }
+
+    #[test]
+    fn it_can_find_fps_dips() {
+        let frames = Frames::new(
+            [90, 90, 45, 90, 85, 90, 30, 10, 90]
+                .iter()
+                .map(|fps| {
+                    let mut frame = generate_random_frame();
+                    frame.fps = *fps;
+
+                    frame
+                })
+                .collect(),
+        );
+
+        assert_eq!(frames.fps_dips(), vec![2, 7]);
+        assert!(Frames::new(Vec::new()).fps_dips().is_empty());
+
+        let frames = Frames::new(
+            [ReplayInt::MIN, ReplayInt::MIN, ReplayInt::MIN]
+                .iter()
+                .map(|fps| {
+                    let mut frame = generate_random_frame();
+                    frame.fps = *fps;
+
+                    frame
+                })
+                .collect(),
+        );
+        assert!(frames.fps_dips().is_empty());
+    }
+
+    #[test]
+    fn it_can_compute_hand_separation_and_crossovers() {
+        let frames = Frames::new(
+            [
+                (-0.3, 0.3),
+                (-0.1, 0.1),
+                (0.0, 0.0),
+                (0.2, -0.1),
+                (0.2, 0.4),
+                (0.3, 0.5),
+            ]
+            .iter()
+            .enumerate()
+            .map(|(idx, (left_x, right_x))| {
+                let mut frame = generate_random_frame();
+                frame.time = idx as ReplayTime;
+                frame.left_hand.position = Vector3 {
+                    x: *left_x,
+                    y: 1.0,
+                    z: 0.0,
+                };
+                frame.right_hand.position = Vector3 {
+                    x: *right_x,
+                    y: 1.0,
+                    z: 0.0,
+                };
+
+                frame
+            })
+            .collect(),
+        );
+
+        let separation = frames.hand_separation();
+        assert_eq!(separation.len(), 6);
+        assert_eq!(separation[2], (2.0, 0.0));
+        assert!((separation[0].1 - 0.6).abs() < 0.0001);
+        assert!((separation[3].1 - 0.3).abs() < 0.0001);
+
+        assert_eq!(frames.crossovers(), vec![3.0, 4.0]);
+        assert!(Frames::new(Vec::new()).crossovers().is_empty());
+    }
+
+    #[test]
+    fn it_can_detect_fixed_timestep() {
+        let frames_at = |times: &[ReplayTime]| {
+            Frames::new(
+                times
+                    .iter()
+                    .map(|time| {
+                        let mut frame = generate_random_frame();
+                        frame.time = *time;
+
+                        frame
+                    })
+                    .collect(),
+            )
+        };
+
+        let fixed: Vec<ReplayTime> = (0..100).map(|i| i as ReplayTime / 90.0).collect();
+        assert!(frames_at(&fixed).is_fixed_timestep(0.0001));
+
+        let variable = [0.0, 0.011, 0.018, 0.032, 0.04, 0.056];
+        assert!(!frames_at(&variable).is_fixed_timestep(0.001));
+        assert!(frames_at(&variable).is_fixed_timestep(0.01));
+
+        assert!(frames_at(&[]).is_fixed_timestep(0.0));
+    }
+
+    #[test]
+    fn it_can_iterate_frames() -> Result<()> {
+        let frames = Frames::new(Vec::from([
+            generate_random_frame(),
+            generate_random_frame(),
+            generate_random_frame(),
+        ]));
+        let buf = get_frames_buffer(&frames)?;
+
+        let iterated = FrameIterator::new(Cursor::new(buf.clone()))?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(iterated, *Frames::load(&mut Cursor::new(buf.clone()))?);
+
+        let mut truncated = FrameIterator::new(Cursor::new(&buf[..buf.len() - 1]))?;
+        assert!(truncated.next().unwrap().is_ok());
+        assert!(truncated.next().unwrap().is_ok());
+        assert!(truncated.next().unwrap().is_err());
+        assert!(truncated.next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_iterate_indexed_frames() -> Result<()> {
+        let replay = generate_random_replay();
+        let reader = &mut Cursor::new(get_replay_buffer(&replay)?);
+        let index = ReplayIndex::index(reader)?;
+
+        let iterated = index.frames.iter(reader)?.collect::<Result<Vec<_>>>()?;
+
+        assert_eq!(iterated, *replay.frames);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_compute_hand_efficiency() {
+        let frames = Frames::new(
+            [
+                (0.0, 0.0),
+                (1.0, 0.5),
+                (0.0, 1.0),
+                (1.0, 1.5),
+                (0.0, 2.0),
+                (1.0, 2.5),
+            ]
+            .iter()
+            .map(|(left_x, right_x)| {
+                let mut frame = generate_random_frame();
+                frame.left_hand.position = Vector3 {
+                    x: *left_x,
+                    y: 0.0,
+                    z: 0.0,
+                };
+                frame.right_hand.position = Vector3 {
+                    x: *right_x,
+                    y: 0.0,
+                    z: 0.0,
+                };
+
+                frame
+            })
+            .collect(),
+        );
+
+        // left hand swings back and forth, right hand moves straight
+        let (left, right) = frames.hand_efficiency();
+        assert!((left - 5.0).abs() < 0.0001);
+        assert!((right - 1.0).abs() < 0.0001);
+
+        let back_to_start = Frames::new(frames[..5].to_vec());
+        assert_eq!(back_to_start.hand_efficiency().0, 0.0);
+        assert_eq!(Frames::new(Vec::new()).hand_efficiency(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn it_can_compute_fps_stats() {
+        let frames = Frames::new(
+            [90, 72, 144, 90]
+                .iter()
+                .map(|fps| {
+                    let mut frame = generate_random_frame();
+                    frame.fps = *fps;
+
+                    frame
+                })
+                .collect(),
+        );
+
+        assert_eq!(
+            frames.fps_stats(),
+            Some(FpsStats {
+                min: 72,
+                max: 144,
+                mean: 99.0,
+                median: 90.0,
+            })
+        );
+
+        let odd = Frames::new(frames[..3].to_vec());
+        assert_eq!(odd.fps_stats().map(|s| s.median), Some(90.0));
+
+        assert_eq!(Frames::new(Vec::new()).fps_stats(), None);
+    }
+
+    #[test]
+    fn it_can_interpolate_pose_at_time() {
+        let mut first = generate_random_frame();
+        first.time = 1.0;
+        first.fps = 90;
+        first.head.position = Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        first.head.rotation = Vector4 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        };
+        let mut second = generate_random_frame();
+        second.time = 2.0;
+        second.fps = 72;
+        second.head.position = Vector3 {
+            x: 2.0,
+            y: 4.0,
+            z: 6.0,
+        };
+        second.head.rotation = Vector4 {
+            x: 0.0,
+            y: std::f32::consts::FRAC_1_SQRT_2,
+            z: 0.0,
+            w: std::f32::consts::FRAC_1_SQRT_2,
+        };
+        let frames = Frames::new(Vec::from([first.clone(), second.clone()]));
+
+        let pose = frames.pose_at(1.5).unwrap();
+        assert_eq!(pose.time, 1.5);
+        assert_eq!(pose.fps, 90);
+        assert_eq!(
+            pose.head.position,
+            Vector3 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0
+            }
+        );
+        assert!((pose.head.rotation.y - 0.382_683).abs() < 0.0001);
+        assert!((pose.head.rotation.w - 0.923_880).abs() < 0.0001);
+        assert_eq!(
+            pose.left_hand.position,
+            first
+                .left_hand
+                .position
+                .lerp(&second.left_hand.position, 0.5)
+        );
+
+        assert_eq!(frames.pose_at(1.0).unwrap().head, first.head);
+        assert_eq!(frames.pose_at(2.0), Some(second));
+        assert_eq!(frames.pose_at(0.5), None);
+        assert_eq!(frames.pose_at(2.5), None);
+        assert_eq!(Frames::new(Vec::new()).pose_at(1.0), None);
+    }
+
+    #[test]
+    fn it_can_write_frames_as_csv() -> Result<()> {
+        let pose = |offset: ReplayFloat| PositionAndRotation {
+            position: Vector3 {
+                x: offset,
+                y: offset + 0.5,
+                z: -offset,
+            },
+            rotation: Vector4 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                w: 1.0,
+            },
+        };
+        let frames = Frames::new(Vec::from([
+            Frame {
+                time: 0.25,
+                fps: 90,
+                head: pose(1.0),
+                left_hand: pose(2.0),
+                right_hand: pose(3.0),
+            },
+            Frame {
+                time: 0.5,
+                fps: 72,
+                head: pose(1.5),
+                left_hand: pose(2.5),
+                right_hand: pose(3.5),
+            },
+        ]));
+
+        let mut buf = Vec::new();
+        frames.to_csv(&mut buf)?;
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "time,fps,\
+             head_position_x,head_position_y,head_position_z,\
+             head_rotation_x,head_rotation_y,head_rotation_z,head_rotation_w,\
+             left_hand_position_x,left_hand_position_y,left_hand_position_z,\
+             left_hand_rotation_x,left_hand_rotation_y,left_hand_rotation_z,left_hand_rotation_w,\
+             right_hand_position_x,right_hand_position_y,right_hand_position_z,\
+             right_hand_rotation_x,right_hand_rotation_y,right_hand_rotation_z,right_hand_rotation_w\n\
+             0.25,90,1,1.5,-1,0,0,0,1,2,2.5,-2,0,0,0,1,3,3.5,-3,0,0,0,1\n\
+             0.5,72,1.5,2,-1.5,0,0,0,1,2.5,3,-2.5,0,0,0,1,3.5,4,-3.5,0,0,0,1\n"
+        );
+
+        let mut buf = Vec::new();
+        Frames::new(Vec::new()).to_csv(&mut buf)?;
+        assert_eq!(String::from_utf8(buf).unwrap().lines().count(), 1);
+
+        Ok(())
+    }
 }
@@ -1,6 +1,7 @@
-use super::{error::BsorError, read_utils, Result};
-use crate::replay::BSOR_MAGIC;
-use std::io::Read;
+use super::{error::BsorError, read_utils, write_utils, Result};
+use crate::replay::{GetStaticBlockSize, ReplayInt, BSOR_MAGIC};
+use std::io::{Read, Write};
+use std::mem::size_of;
 
 pub(crate) struct Header {
     pub version: u8,
@@ -21,6 +22,17 @@ impl Header {
 
         Ok(Self { version })
     }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_utils::write_int(w, BSOR_MAGIC)?;
+        write_utils::write_byte(w, self.version)
+    }
+}
+
+impl GetStaticBlockSize for Header {
+    fn get_static_size() -> usize {
+        size_of::<ReplayInt>() + size_of::<u8>()
+    }
 }
 
 #[cfg(test)]
@@ -60,6 +72,17 @@ mod tests {
         assert_eq!(invalid_version, reported_version)
     }
 
+    #[test]
+    fn it_can_write_header() -> Result<()> {
+        let mut buf = Vec::new();
+        Header { version: 1 }.write(&mut buf)?;
+
+        assert_eq!(buf.len(), Header::get_static_size());
+        assert_eq!(Header::load(&mut Cursor::new(buf))?.version, 1);
+
+        Ok(())
+    }
+
     #[test]
     fn it_can_load_header() -> Result<()> {
         let mut buf = ReplayInt::to_le_bytes(BSOR_MAGIC).to_vec();
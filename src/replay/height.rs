@@ -1,42 +1,46 @@
 //! structs storing the Heights block data
-use super::{read_utils, BsorError, ReplayTime, Result};
+use super::{read_utils, write_utils, ReplayTime, Result};
 use crate::replay::{
-    assert_start_of_block, BlockIndex, BlockType, GetStaticBlockSize, LoadBlock, LoadRealBlockSize,
-    ReplayFloat, ReplayInt,
+    expect_block, load_block_items, write_block_items, ApproxEq, BlockIndex, BlockType,
+    GetStaticBlockSize, LoadBlock, LoadOptions, LoadRealBlockSize, LoadWarning, ReplayFloat,
+    ReplayInt,
 };
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::marker::PhantomData;
 use std::mem::size_of;
 use std::ops::Deref;
 
 /// Struct implements [std::ops::Deref] trait so it could be treated as Vec<[Height]>
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Heights(Vec<Height>);
 
 impl Heights {
-    #[cfg(test)]
-    pub(crate) fn new(vec: Vec<Height>) -> Heights {
+    /// Creates block from given items
+    pub fn new(vec: Vec<Height>) -> Heights {
         Heights(vec)
     }
 
-    pub(crate) fn load<R: Read>(r: &mut R) -> Result<Heights> {
-        match read_utils::read_byte(r) {
-            Ok(v) => {
-                if v != 4 {
-                    return Err(BsorError::InvalidBsor);
-                }
-            }
-            Err(e) => return Err(e),
-        }
+    pub(crate) fn load<RS: Read + Seek>(r: &mut RS) -> Result<Heights> {
+        let offset = r.stream_position()?;
 
-        let count = read_utils::read_int(r)? as usize;
-        let mut vec = Vec::<Height>::with_capacity(count);
-
-        for _ in 0..count {
-            vec.push(Height::load(r)?);
-        }
+        Self::load_with_options(r, offset, &LoadOptions::default(), &mut Vec::new())
+    }
 
-        Ok(Heights(vec))
+    pub(crate) fn load_with_options<R: Read>(
+        r: &mut R,
+        offset: u64,
+        options: &LoadOptions,
+        warnings: &mut Vec<LoadWarning>,
+    ) -> Result<Heights> {
+        Ok(Heights(load_block_items(
+            r,
+            BlockType::Heights,
+            offset,
+            options,
+            warnings,
+            Height::load,
+        )?))
     }
 
     pub(crate) fn load_block<RS: Read + Seek>(
@@ -47,6 +51,11 @@ impl Heights {
 
         Self::load(r)
     }
+
+    /// Writes the block id, items count and all items
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_block_items(w, BlockType::Heights, &self.0, Height::write)
+    }
 }
 
 impl Deref for Heights {
@@ -66,17 +75,19 @@ impl GetStaticBlockSize for Heights {
 impl LoadBlock for BlockIndex<Heights> {
     type Item = Heights;
 
-    /// Loads Frames block from ReplayIndex
+    /// Loads Heights block from ReplayIndex
     fn load<RS: Read + Seek>(&self, r: &mut RS) -> Result<Self::Item> {
         Self::Item::load_block(r, self)
     }
 }
 
+impl_block!(Heights, Height, [time]);
+
 impl LoadRealBlockSize for Heights {
     type Item = Heights;
 
     fn load_real_block_size<RS: Read + Seek>(r: &mut RS, pos: u64) -> Result<BlockIndex<Heights>> {
-        assert_start_of_block(r, BlockType::Heights)?;
+        expect_block(r, BlockType::Heights, pos)?;
 
         let count = read_utils::read_int(r)?;
 
@@ -90,9 +101,15 @@ impl LoadRealBlockSize for Heights {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Height {
     pub height: ReplayFloat,
+    /// song time the player height changed at, in seconds
     pub time: ReplayTime,
 }
 
@@ -103,6 +120,11 @@ impl Height {
 
         Ok(Self { height, time })
     }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_utils::write_float(w, self.height)?;
+        write_utils::write_float(w, self.time)
+    }
 }
 
 impl GetStaticBlockSize for Height {
@@ -111,6 +133,12 @@ impl GetStaticBlockSize for Height {
     }
 }
 
+impl ApproxEq for Height {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.height.approx_eq(&other.height) && self.time.approx_eq(&other.time)
+    }
+}
+
 impl LoadRealBlockSize for Height {
     type Item = Height;
 }
@@ -118,9 +146,22 @@ impl LoadRealBlockSize for Height {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::replay::BsorError;
     use crate::tests_util::{append_height, generate_random_height, get_heights_buffer};
     use std::io::Cursor;
 
+    #[test]
+    fn it_can_access_heights_by_index_and_get_vec() {
+        let items = vec![generate_random_height(), generate_random_height()];
+        let heights = Heights(items.clone());
+
+        assert_eq!(heights.len(), 2);
+        assert!(!heights.is_empty());
+        assert_eq!(heights[1], items[1]);
+        assert_eq!(heights.get_vec(), &items);
+        assert!(Heights(Vec::new()).get_vec().is_empty());
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_height() {
         assert_eq!(Height::get_static_size(), 8);
@@ -138,13 +179,25 @@ mod tests {
         assert_eq!(result, wall)
     }
 
+    #[test]
+    fn it_can_write_heights() -> Result<()> {
+        let heights = Vec::from([generate_random_height(), generate_random_height()]);
+
+        let mut buf = Vec::new();
+        Heights::new(heights.clone()).write(&mut buf)?;
+
+        assert_eq!(buf, get_heights_buffer(&heights)?);
+
+        Ok(())
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_heights() {
         assert_eq!(Heights::get_static_size(), 5);
     }
 
     #[test]
-    fn it_returns_invalid_bsor_error_when_heights_block_id_is_invalid() -> Result<()> {
+    fn it_returns_unexpected_block_error_when_heights_block_id_is_invalid() -> Result<()> {
         let heights = Vec::from([generate_random_height(), generate_random_height()]);
 
         let mut buf = get_heights_buffer(&heights)?;
@@ -152,7 +205,10 @@ mod tests {
 
         let result = Heights::load(&mut Cursor::new(buf));
 
-        assert!(matches!(result, Err(BsorError::InvalidBsor)));
+        assert!(matches!(
+            result,
+            Err(BsorError::UnexpectedBlock { found: 255, .. })
+        ));
 
         Ok(())
     }
@@ -1,9 +1,34 @@
 //! structs storing the Info block data
-use super::read_utils::{read_bool, read_float, read_int, read_string};
-use crate::replay::{assert_start_of_block, BlockType, ReplayFloat, ReplayInt, ReplayTime, Result};
-use std::io::Read;
+use super::device::{Controller, Hmd};
+use super::header::Header;
+use super::modifier::Modifier;
+use super::read_utils::{
+    read_bool, read_float, read_int, read_str_slice, read_string_bounded, read_string_lossy,
+    PeekReader,
+};
+use super::write_utils::{write_bool, write_byte, write_float, write_int, write_string};
+use crate::replay::{
+    expect_block, ApproxEq, BlockType, BsorError, GetStaticBlockSize, LoadOptions, ReplayFloat,
+    ReplayInt, ReplayTime, Result,
+};
+use std::collections::HashSet;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::mem::size_of;
 
-#[derive(PartialEq, Debug)]
+/// Returns stream offset of the Info block, which always directly follows the header
+fn info_offset() -> u64 {
+    Header::get_static_size() as u64
+}
+
+/// Max length in bytes of any Info string, longer strings are treated as a corrupted replay
+pub const MAX_INFO_STRING_LEN: usize = 64 * 1024;
+
+#[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Info {
     pub version: String,
     pub game_version: String,
@@ -25,32 +50,56 @@ pub struct Info {
     pub jump_distance: ReplayFloat,
     pub left_handed: bool,
     pub height: ReplayFloat,
+    /// song time the replay starts at, in seconds
     pub start_time: ReplayTime,
+    /// song time the player failed at, in seconds (0 if not failed)
     pub fail_time: ReplayTime,
+    /// song speed multiplier, not a time despite the type
     pub speed: ReplayTime,
 }
 
 impl Info {
     pub(crate) fn load<R: Read>(r: &mut R) -> Result<Info> {
-        assert_start_of_block(r, BlockType::Info)?;
-
-        let version = read_string(r)?;
-        let game_version = read_string(r)?;
-        let timestamp = read_string(r)?.parse()?;
-        let player_id = read_string(r)?;
-        let player_name = read_string(r)?;
-        let platform = read_string(r)?;
-        let tracking_system = read_string(r)?;
-        let hmd = read_string(r)?;
-        let controller = read_string(r)?;
-        let hash = read_string(r)?;
-        let song_name = read_string(r)?;
-        let mapper = read_string(r)?;
-        let difficulty = read_string(r)?;
+        Self::load_with(r, MAX_INFO_STRING_LEN, read_string_bounded)
+    }
+
+    /// Loads Info block rejecting strings longer than [LoadOptions::max_string_len]
+    pub(crate) fn load_with_options<R: Read>(r: &mut R, options: &LoadOptions) -> Result<Info> {
+        Self::load_with(r, options.max_string_len, read_string_bounded)
+    }
+
+    /// Loads Info block decoding strings lossily, see [LoadOptions::lossy_strings]
+    pub(crate) fn load_lossy<R: Read>(
+        r: &mut PeekReader<R>,
+        options: &LoadOptions,
+    ) -> Result<Info> {
+        Self::load_with(r, options.max_string_len, read_string_lossy)
+    }
+
+    fn load_with<R: Read>(
+        r: &mut R,
+        max_string_len: usize,
+        read_string: fn(&mut R, usize) -> Result<String>,
+    ) -> Result<Info> {
+        expect_block(r, BlockType::Info, info_offset())?;
+
+        let version = read_string(r, max_string_len)?;
+        let game_version = read_string(r, max_string_len)?;
+        let timestamp = read_string(r, max_string_len)?.parse()?;
+        let player_id = read_string(r, max_string_len)?;
+        let player_name = read_string(r, max_string_len)?;
+        let platform = read_string(r, max_string_len)?;
+        let tracking_system = read_string(r, max_string_len)?;
+        let hmd = read_string(r, max_string_len)?;
+        let controller = read_string(r, max_string_len)?;
+        let hash = read_string(r, max_string_len)?;
+        let song_name = read_string(r, max_string_len)?;
+        let mapper = read_string(r, max_string_len)?;
+        let difficulty = read_string(r, max_string_len)?;
         let score = read_int(r)?;
-        let mode = read_string(r)?;
-        let environment = read_string(r)?;
-        let modifiers = read_string(r)?;
+        let mode = read_string(r, max_string_len)?;
+        let environment = read_string(r, max_string_len)?;
+        let modifiers = read_string(r, max_string_len)?;
         let jump_distance = read_float(r)?;
         let left_handed = read_bool(r)?;
         let height = read_float(r)?;
@@ -84,26 +133,317 @@ impl Info {
             speed,
         })
     }
+
+    /// Seeks past the Info block, reading only string lengths
+    pub(crate) fn skip<RS: Read + Seek>(r: &mut RS) -> Result<()> {
+        expect_block(r, BlockType::Info, info_offset())?;
+
+        // version .. difficulty
+        for _ in 0..13 {
+            skip_string(r)?;
+        }
+        // score
+        r.seek(SeekFrom::Current(size_of::<ReplayInt>() as i64))?;
+        // mode, environment, modifiers
+        for _ in 0..3 {
+            skip_string(r)?;
+        }
+        // jump_distance, left_handed, height, start_time, fail_time, speed
+        r.seek(SeekFrom::Current(
+            (size_of::<ReplayFloat>() * 5 + size_of::<u8>()) as i64,
+        ))?;
+
+        Ok(())
+    }
+
+    /// Returns whether the run was completed, abandoned runs are stored with a negative score (-1)
+    pub fn is_complete(&self) -> bool {
+        self.score >= 0
+    }
+
+    /// Returns total score multiplier of the modifiers, i.e. 1 plus the sum of each distinct modifier multiplier change, or 0 if any disqualifying modifier is present
+    pub fn score_multiplier(&self) -> ReplayFloat {
+        let modifiers = self.modifiers_parsed().into_iter().collect::<HashSet<_>>();
+        if modifiers.iter().any(Modifier::is_disqualifying) {
+            return 0.0;
+        }
+
+        1.0 + modifiers
+            .iter()
+            .map(Modifier::score_multiplier_change)
+            .sum::<ReplayFloat>()
+    }
+
+    /// Returns [Info::timestamp] as UTC date and time
+    #[cfg(feature = "chrono")]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp(self.timestamp as i64, 0)
+            .expect("any u32 timestamp is in range")
+    }
+
+    /// Returns known modifiers turned on, unknown codes are ignored
+    pub fn modifiers_parsed(&self) -> Vec<Modifier> {
+        Modifier::parse_list(&self.modifiers)
+    }
+
+    /// Replaces modifiers with given ones, the inverse of [Info::modifiers_parsed()]
+    pub fn set_modifiers(&mut self, modifiers: &[Modifier]) {
+        self.modifiers = Modifier::join_list(modifiers);
+    }
+
+    /// Returns whether the map hash matches given one, ignoring case, surrounding whitespace and the `custom_level_` level id prefix
+    pub fn matches_hash(&self, expected: &str) -> bool {
+        normalize_hash(&self.hash).eq_ignore_ascii_case(normalize_hash(expected))
+    }
+
+    /// Returns standardized HMD kind, see [Hmd::parse()]
+    pub fn hmd_kind(&self) -> Hmd {
+        Hmd::parse(&self.hmd)
+    }
+
+    /// Returns standardized controller kind, see [Controller::parse()]
+    pub fn controller_kind(&self) -> Controller {
+        Controller::parse(&self.controller)
+    }
+
+    /// Returns whether the replay was played with given modifier, e.g. `"NF"` for No Fail
+    pub fn has_modifier(&self, code: &str) -> bool {
+        Modifier::from_code(code.trim())
+            .is_some_and(|modifier| self.modifiers_parsed().contains(&modifier))
+    }
+
+    /// Writes the block id followed by all info fields
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_byte(w, BlockType::Info.try_into()?)?;
+
+        write_string(w, &self.version)?;
+        write_string(w, &self.game_version)?;
+        write_string(w, &self.timestamp.to_string())?;
+        write_string(w, &self.player_id)?;
+        write_string(w, &self.player_name)?;
+        write_string(w, &self.platform)?;
+        write_string(w, &self.tracking_system)?;
+        write_string(w, &self.hmd)?;
+        write_string(w, &self.controller)?;
+        write_string(w, &self.hash)?;
+        write_string(w, &self.song_name)?;
+        write_string(w, &self.mapper)?;
+        write_string(w, &self.difficulty)?;
+        write_int(w, self.score)?;
+        write_string(w, &self.mode)?;
+        write_string(w, &self.environment)?;
+        write_string(w, &self.modifiers)?;
+        write_float(w, self.jump_distance)?;
+        write_bool(w, self.left_handed)?;
+        write_float(w, self.height)?;
+        write_float(w, self.start_time)?;
+        write_float(w, self.fail_time)?;
+        write_float(w, self.speed)
+    }
+}
+
+impl ApproxEq for Info {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.version == other.version
+            && self.game_version == other.game_version
+            && self.timestamp == other.timestamp
+            && self.player_id == other.player_id
+            && self.player_name == other.player_name
+            && self.platform == other.platform
+            && self.tracking_system == other.tracking_system
+            && self.hmd == other.hmd
+            && self.controller == other.controller
+            && self.hash == other.hash
+            && self.song_name == other.song_name
+            && self.mapper == other.mapper
+            && self.difficulty == other.difficulty
+            && self.score == other.score
+            && self.mode == other.mode
+            && self.environment == other.environment
+            && self.modifiers == other.modifiers
+            && self.jump_distance.approx_eq(&other.jump_distance)
+            && self.left_handed == other.left_handed
+            && self.height.approx_eq(&other.height)
+            && self.start_time.approx_eq(&other.start_time)
+            && self.fail_time.approx_eq(&other.fail_time)
+            && self.speed.approx_eq(&other.speed)
+    }
+}
+
+fn normalize_hash(hash: &str) -> &str {
+    const LEVEL_ID_PREFIX: &str = "custom_level_";
+
+    let hash = hash.trim();
+    match hash.get(..LEVEL_ID_PREFIX.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(LEVEL_ID_PREFIX) => {
+            &hash[LEVEL_ID_PREFIX.len()..]
+        }
+        _ => hash,
+    }
+}
+
+fn skip_string<RS: Read + Seek>(r: &mut RS) -> Result<()> {
+    let len = read_int(r)?;
+    if len < 0 || len as usize > MAX_INFO_STRING_LEN {
+        return Err(BsorError::InvalidBsor);
+    }
+
+    r.seek(SeekFrom::Current(len as i64))?;
+
+    Ok(())
+}
+
+/// Zero-allocation view of the Info block, borrowing all strings from an in-memory buffer
+#[derive(PartialEq, Clone, Debug)]
+pub struct InfoView<'a> {
+    pub version: &'a str,
+    pub game_version: &'a str,
+    pub timestamp: u32,
+    pub player_id: &'a str,
+    pub player_name: &'a str,
+    pub platform: &'a str,
+    pub tracking_system: &'a str,
+    pub hmd: &'a str,
+    pub controller: &'a str,
+    pub hash: &'a str,
+    pub song_name: &'a str,
+    pub mapper: &'a str,
+    pub difficulty: &'a str,
+    pub score: ReplayInt,
+    pub mode: &'a str,
+    pub environment: &'a str,
+    pub modifiers: &'a str,
+    pub jump_distance: ReplayFloat,
+    pub left_handed: bool,
+    pub height: ReplayFloat,
+    /// song time the replay starts at, in seconds
+    pub start_time: ReplayTime,
+    /// song time the player failed at, in seconds (0 if not failed)
+    pub fail_time: ReplayTime,
+    /// song speed multiplier, not a time despite the type
+    pub speed: ReplayTime,
+}
+
+impl<'a> InfoView<'a> {
+    /// Parses Info block of a whole replay file buffer, validating its header first
+    pub fn from_replay(buf: &'a [u8]) -> Result<InfoView<'a>> {
+        let mut r = buf;
+        Header::load(&mut r)?;
+
+        Self::load(r)
+    }
+
+    /// Parses Info block from a buffer starting at the block id
+    pub fn load(buf: &'a [u8]) -> Result<InfoView<'a>> {
+        let r = &mut &buf[..];
+
+        expect_block(r, BlockType::Info, info_offset())?;
+
+        let version = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let game_version = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let timestamp = read_str_slice(r, MAX_INFO_STRING_LEN)?.parse()?;
+        let player_id = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let player_name = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let platform = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let tracking_system = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let hmd = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let controller = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let hash = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let song_name = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let mapper = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let difficulty = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let score = read_int(r)?;
+        let mode = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let environment = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let modifiers = read_str_slice(r, MAX_INFO_STRING_LEN)?;
+        let jump_distance = read_float(r)?;
+        let left_handed = read_bool(r)?;
+        let height = read_float(r)?;
+        let start_time = read_float(r)?;
+        let fail_time = read_float(r)?;
+        let speed = read_float(r)?;
+
+        Ok(InfoView {
+            version,
+            game_version,
+            timestamp,
+            player_id,
+            player_name,
+            platform,
+            tracking_system,
+            hmd,
+            controller,
+            hash,
+            song_name,
+            mapper,
+            difficulty,
+            score,
+            mode,
+            environment,
+            modifiers,
+            jump_distance,
+            left_handed,
+            height,
+            start_time,
+            fail_time,
+            speed,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::replay::BsorError;
-    use crate::tests_util::{append_info, generate_random_info};
+    use crate::tests_util::{
+        append_info, generate_random_info, generate_random_replay, get_replay_buffer,
+    };
     use std::io::Cursor;
 
     #[test]
-    fn it_returns_invalid_bsor_error_when_info_block_id_is_invalid() -> Result<()> {
+    fn it_returns_unexpected_block_error_when_info_block_id_is_invalid() -> Result<()> {
         let buf = Vec::from([255u8]);
 
         let result = Info::load(&mut Cursor::new(buf));
 
+        assert!(matches!(
+            result,
+            Err(BsorError::UnexpectedBlock { found: 255, .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_invalid_bsor_error_when_info_string_length_is_too_big() -> Result<()> {
+        let info_id = BlockType::Info.try_into()?;
+        let mut buf = Vec::from([info_id]);
+        buf.append(&mut ReplayInt::to_le_bytes(1024 * 1024 * 1024).to_vec());
+        buf.append(&mut "0.5.4".as_bytes().to_vec());
+
+        let result = Info::load(&mut Cursor::new(buf));
+
         assert!(matches!(result, Err(BsorError::InvalidBsor)));
 
         Ok(())
     }
 
+    #[test]
+    fn it_can_write_info() -> Result<()> {
+        let info = generate_random_info();
+
+        let info_id = BlockType::Info.try_into()?;
+        let mut expected = Vec::from([info_id]);
+        append_info(&mut expected, &info)?;
+
+        let mut buf = Vec::new();
+        info.write(&mut buf)?;
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+
     #[test]
     fn it_can_load_info() -> Result<()> {
         let info = generate_random_info();
@@ -119,4 +459,185 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn it_can_load_info_view_equal_to_owned_info() -> Result<()> {
+        let info = generate_random_info();
+
+        let info_id = BlockType::Info.try_into()?;
+        let mut buf = Vec::from([info_id]);
+        append_info(&mut buf, &info)?;
+
+        let view = InfoView::load(&buf)?;
+
+        assert_eq!(view.version, info.version);
+        assert_eq!(view.game_version, info.game_version);
+        assert_eq!(view.timestamp, info.timestamp);
+        assert_eq!(view.player_id, info.player_id);
+        assert_eq!(view.player_name, info.player_name);
+        assert_eq!(view.platform, info.platform);
+        assert_eq!(view.tracking_system, info.tracking_system);
+        assert_eq!(view.hmd, info.hmd);
+        assert_eq!(view.controller, info.controller);
+        assert_eq!(view.hash, info.hash);
+        assert_eq!(view.song_name, info.song_name);
+        assert_eq!(view.mapper, info.mapper);
+        assert_eq!(view.difficulty, info.difficulty);
+        assert_eq!(view.score, info.score);
+        assert_eq!(view.mode, info.mode);
+        assert_eq!(view.environment, info.environment);
+        assert_eq!(view.modifiers, info.modifiers);
+        assert_eq!(view.jump_distance, info.jump_distance);
+        assert_eq!(view.left_handed, info.left_handed);
+        assert_eq!(view.height, info.height);
+        assert_eq!(view.start_time, info.start_time);
+        assert_eq!(view.fail_time, info.fail_time);
+        assert_eq!(view.speed, info.speed);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_load_info_view_from_replay_buffer() -> Result<()> {
+        let replay = generate_random_replay();
+        let buf = get_replay_buffer(&replay)?;
+
+        let view = InfoView::from_replay(&buf)?;
+
+        assert_eq!(view.player_name, replay.info.player_name);
+        assert_eq!(view.hash, replay.info.hash);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_io_error_when_info_view_string_is_truncated() -> Result<()> {
+        let info_id = BlockType::Info.try_into()?;
+        let mut buf = Vec::from([info_id]);
+        buf.append(&mut ReplayInt::to_le_bytes(10).to_vec());
+        buf.append(&mut "0.5".as_bytes().to_vec());
+
+        let result = InfoView::load(&buf);
+
+        assert!(matches!(result, Err(BsorError::Io(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_check_modifiers() {
+        let mut info = generate_random_info();
+        info.modifiers = "DA, FS".to_owned();
+
+        assert!(info.has_modifier("DA"));
+        assert!(info.has_modifier("FS"));
+        assert!(!info.has_modifier("NF"));
+        assert!(!info.has_modifier("D"));
+
+        info.modifiers = String::new();
+        assert!(!info.has_modifier(""));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn it_can_convert_timestamp_to_datetime() {
+        let mut info = generate_random_info();
+        info.timestamp = 1_700_000_000;
+
+        assert_eq!(info.datetime().to_rfc3339(), "2023-11-14T22:13:20+00:00");
+
+        info.timestamp = 0;
+        assert_eq!(info.datetime().to_rfc3339(), "1970-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn it_can_parse_modifiers() {
+        let mut info = generate_random_info();
+        info.modifiers = "DA,FS,NF,ZZ".to_owned();
+
+        assert_eq!(
+            info.modifiers_parsed(),
+            vec![
+                Modifier::DisappearingArrows,
+                Modifier::FasterSong,
+                Modifier::NoFail
+            ]
+        );
+
+        info.set_modifiers(&[Modifier::GhostNotes, Modifier::ProMode]);
+        assert_eq!(info.modifiers, "GN,PM");
+        assert_eq!(
+            info.modifiers_parsed(),
+            vec![Modifier::GhostNotes, Modifier::ProMode]
+        );
+
+        info.modifiers = String::new();
+        assert!(info.modifiers_parsed().is_empty());
+    }
+
+    #[test]
+    fn it_can_skip_info() -> Result<()> {
+        let info = generate_random_info();
+
+        let info_id = BlockType::Info.try_into()?;
+        let mut buf = Vec::from([info_id]);
+        append_info(&mut buf, &info)?;
+        let len = buf.len() as u64;
+
+        let mut r = Cursor::new(buf);
+        Info::skip(&mut r)?;
+
+        assert_eq!(r.position(), len);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_parse_device_kinds() {
+        let mut info = generate_random_info();
+        info.hmd = "Quest_2".to_owned();
+        info.controller = "Oculus Touch".to_owned();
+
+        assert_eq!(info.hmd_kind(), Hmd::Quest2);
+        assert_eq!(info.controller_kind(), Controller::OculusTouch);
+    }
+
+    #[test]
+    fn it_can_check_whether_run_is_complete() {
+        let mut info = generate_random_info();
+
+        info.score = 0;
+        assert!(info.is_complete());
+
+        info.score = -1;
+        assert!(!info.is_complete());
+    }
+
+    #[test]
+    fn it_can_compute_score_multiplier() {
+        let mut info = generate_random_info();
+
+        info.modifiers = String::new();
+        assert_eq!(info.score_multiplier(), 1.0);
+
+        info.modifiers = "DA,FS".to_owned();
+        assert!((info.score_multiplier() - 1.15).abs() < 1e-6);
+
+        info.modifiers = "FS,FS".to_owned();
+        assert!((info.score_multiplier() - 1.08).abs() < 1e-6);
+
+        info.modifiers = "NF,FS,ZM".to_owned();
+        assert_eq!(info.score_multiplier(), 0.0);
+    }
+
+    #[test]
+    fn it_can_match_map_hash() {
+        let mut info = generate_random_info();
+        info.hash = "A1B2C3D4E5F60718293A4B5C6D7E8F9012345678".to_owned();
+
+        assert!(info.matches_hash("a1b2c3d4e5f60718293a4b5c6d7e8f9012345678"));
+        assert!(info.matches_hash(" custom_level_A1B2C3D4E5F60718293A4B5C6D7E8F9012345678\n"));
+        assert!(!info.matches_hash("b1b2c3d4e5f60718293a4b5c6d7e8f9012345678"));
+        assert!(!info.matches_hash("a1b2c3d4e5f60718293a4b5c6d7e8f901234567"));
+    }
 }
@@ -0,0 +1,85 @@
+//! Loading of memory-mapped replay files (requires `mmap` feature)
+//!
+//! The file must not be modified while it is mapped, otherwise the loaded data is undefined
+//!
+//! # Example
+//! ```no_run
+//! use bsor::prelude::*;
+//! use std::fs::File;
+//! use std::io::Cursor;
+//!
+//! let file = File::open("example.bsor").unwrap();
+//!
+//! let (replay_index, map) = ReplayIndex::from_mmap(&file).unwrap();
+//! let notes = replay_index.notes.load(&mut Cursor::new(&map[..])).unwrap();
+//! println!("{} notes", notes.len());
+//! ```
+use super::{Replay, ReplayIndex, Result};
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::Cursor;
+
+impl Replay {
+    /// Maps the file into memory and loads replay from it, see [Replay::from_slice()]
+    pub fn from_mmap(file: &File) -> Result<Replay> {
+        Self::from_slice(&map(file)?)
+    }
+}
+
+impl ReplayIndex {
+    /// Maps the file into memory and indexes it, returning the index along with the mapping to load blocks from
+    ///
+    /// Blocks are loaded by wrapping the mapping in a [Cursor], so seeking to a block is just moving the position within the mapped region
+    pub fn from_mmap(file: &File) -> Result<(ReplayIndex, Mmap)> {
+        let map = map(file)?;
+        let index = Self::index(&mut Cursor::new(&map[..]))?;
+
+        Ok((index, map))
+    }
+}
+
+fn map(file: &File) -> Result<Mmap> {
+    // SAFETY: the mapping is read only and the module docs require the file not to be modified while mapped
+    Ok(unsafe { Mmap::map(file)? })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::LoadBlock;
+    use crate::tests_util::{generate_random_replay, get_replay_buffer};
+
+    #[test]
+    fn it_can_load_mapped_replay() -> Result<()> {
+        let buf = get_replay_buffer(&generate_random_replay())?;
+        let path = std::env::temp_dir().join(format!("bsor-mmap-{}.bsor", std::process::id()));
+        std::fs::write(&path, &buf)?;
+
+        let file = File::open(&path)?;
+        let result = Replay::from_mmap(&file);
+        let index = ReplayIndex::from_mmap(&file).and_then(|(index, map)| {
+            let r = &mut Cursor::new(&map[..]);
+
+            Ok((index.notes.load(r)?, index.frames.load(r)?))
+        });
+        drop(file);
+        std::fs::remove_file(&path)?;
+
+        let replay = Replay::load(&mut Cursor::new(&buf))?;
+
+        let result = result?;
+        assert_eq!(result.version, replay.version);
+        assert_eq!(result.info, replay.info);
+        assert_eq!(result.frames, replay.frames);
+        assert_eq!(result.notes, replay.notes);
+        assert_eq!(result.walls, replay.walls);
+        assert_eq!(result.heights, replay.heights);
+        assert_eq!(result.pauses, replay.pauses);
+
+        let (notes, frames) = index?;
+        assert_eq!(notes, replay.notes);
+        assert_eq!(frames, replay.frames);
+
+        Ok(())
+    }
+}
@@ -47,38 +47,121 @@
 //! | Frames        | 1255kB       |
 //! | Notes         | 137kB        |
 //!
+
+/// Implements methods and traits shared by all block types (Frames, Notes, Walls, Heights and Pauses)
+macro_rules! impl_block {
+    ($block:ident, $item:ident, [$($time:ident),+]) => {
+        impl $block {
+            /// Subtracts given offset from all item times
+            pub(crate) fn shift_times(&mut self, offset: $crate::replay::ReplayTime) {
+                for item in self.0.iter_mut() {
+                    $(item.$time -= offset;)+
+                }
+            }
+
+            /// Returns underlying vector of items
+            pub fn get_vec(&self) -> &Vec<$item> {
+                &self.0
+            }
+
+            /// Serializes the block into its on-wire bytes, including block id and items count
+            pub fn to_bytes(&self) -> $crate::replay::Result<Vec<u8>> {
+                let mut buf = Vec::new();
+                self.write(&mut buf)?;
+
+                Ok(buf)
+            }
+        }
+
+        impl $crate::replay::BlockIndex<$block> {
+            /// Indexes block starting at given position, e.g. a checkpoint saved with [BlockIndex::end_pos()] of the previous block, without re-reading preceding blocks
+            pub fn resume_from<RS: std::io::Read + std::io::Seek>(
+                r: &mut RS,
+                pos: u64,
+            ) -> $crate::replay::Result<Self> {
+                r.seek(std::io::SeekFrom::Start(pos))?;
+
+                <$block as $crate::replay::LoadRealBlockSize>::load_real_block_size(r, pos)
+            }
+        }
+
+        impl<'a> IntoIterator for &'a $block {
+            type Item = &'a $item;
+            type IntoIter = std::slice::Iter<'a, $item>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter()
+            }
+        }
+
+        impl IntoIterator for $block {
+            type Item = $item;
+            type IntoIter = std::vec::IntoIter<$item>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.into_iter()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod columns;
+pub mod device;
 pub mod error;
+pub mod file;
 pub mod frame;
 mod header;
 pub mod height;
 pub mod info;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod modifier;
 pub mod note;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 pub mod pause;
 mod read_utils;
+mod score;
+pub mod time;
 pub mod vector;
 pub mod wall;
+mod write_utils;
+pub mod writer;
 
+use columns::ReplayColumns;
 use error::BsorError;
-use frame::Frames;
+use frame::{Frame, Frames};
 use header::Header;
-use height::Heights;
-use info::Info;
-use note::Notes;
-use pause::Pauses;
+use height::{Height, Heights};
+use info::{Info, MAX_INFO_STRING_LEN};
+use modifier::Modifier;
+use note::{MatchResult, Note, NoteComparison, NoteEventType, Notes};
+use pause::{Pause, Pauses};
+use score::{ScoreMultiplier, INITIAL_ENERGY};
+use std::fs::File;
 use std::io::Seek;
-use std::io::{Read, SeekFrom};
+use std::io::{BufReader, ErrorKind, Read, SeekFrom, Write};
 use std::marker::PhantomData;
-use wall::Walls;
+use std::mem::size_of;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::{fmt, io};
+use time::SongTime;
+use wall::{Wall, Walls};
+use writer::ReplayWriter;
 
-pub(crate) const BSOR_MAGIC: i32 = 0x442d3d69;
+/// Magic number every bsor file starts with
+pub const BSOR_MAGIC: ReplayInt = 0x442d3d69;
 
 /// int type used in replay file
 pub type ReplayInt = i32;
-/// long type used in replay file
-pub type ReplayLong = u64;
+/// long type used in replay file (C# `long`, so it is signed)
+pub type ReplayLong = i64;
 /// float type used in replay file
 pub type ReplayFloat = f32;
-/// time type used in replay file
+/// time type used in replay file, both for song time and durations (see [time::SongTime] and [time::Duration] to tell them apart)
 pub type ReplayTime = ReplayFloat;
 /// type used to store note line index
 pub type LineIdx = u8;
@@ -90,6 +173,11 @@ pub type Result<T> = std::result::Result<T, BsorError>;
 
 /// Basic crate struct corresponding to the structure of the bsor file
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Replay {
     pub version: u8,
     pub info: Info,
@@ -101,26 +189,899 @@ pub struct Replay {
 }
 
 impl Replay {
+    /// Reads only the header, returning the format version after validating the magic
+    pub fn read_header<R: Read>(r: &mut R) -> Result<u8> {
+        Ok(Header::load(r)?.version)
+    }
+
+    /// Returns whether the stream starts with the BSOR magic, reading only its first 4 bytes
+    pub fn is_bsor<R: Read>(r: &mut R) -> bool {
+        read_utils::read_int(r).is_ok_and(|magic| magic == BSOR_MAGIC)
+    }
+
     /// Load replay into memory
     pub fn load<R: Read>(r: &mut R) -> Result<Replay> {
+        let (replay, _) = Self::load_with_options(r, &LoadOptions::default())?;
+
+        Ok(replay)
+    }
+
+    /// Opens the file at given path and loads replay from it
+    ///
+    /// ```no_run
+    /// use bsor::prelude::*;
+    ///
+    /// let replay = Replay::from_path("example.bsor").unwrap();
+    /// println!("{} notes", replay.notes.len());
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Replay> {
+        Self::load(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Loads replay from bytes already in memory, reading the slice directly without wrapping it in a [std::io::Cursor]
+    ///
+    /// Info strings are still copied, use [info::InfoView] to borrow them from the slice instead
+    pub fn from_slice(data: &[u8]) -> Result<Replay> {
+        let mut r = data;
+
+        Self::load(&mut r)
+    }
+
+    /// Load replay into memory, returning it along with the raw bytes read
+    pub fn load_with_bytes<R: Read>(r: &mut R) -> Result<(Replay, Vec<u8>)> {
+        let mut tee = read_utils::TeeReader::new(r);
+        let replay = Self::load(&mut tee)?;
+
+        Ok((replay, tee.buf))
+    }
+
+    /// Writes the whole replay in bsor format, the inverse of [Replay::load()]
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        ReplayWriter::new(self).write(w)
+    }
+
+    /// Writes the replay to a buffer and loads it back, returning [BsorError::RoundTripMismatch] with the first block that differs from the original
+    ///
+    /// Floats are compared with [ROUNDTRIP_EPSILON] tolerance and NaN floats are treated as equal, so replays storing them round-trip too
+    pub fn assert_roundtrip(&self) -> Result<()> {
+        let mut buf = Vec::new();
+        self.write(&mut buf)?;
+
+        let reloaded = Replay::load(&mut buf.as_slice())?;
+
+        if reloaded.version != self.version || !reloaded.info.approx_eq(&self.info) {
+            return Err(BsorError::RoundTripMismatch(BlockType::Info));
+        }
+
+        let blocks = [
+            (
+                BlockType::Frames,
+                reloaded.frames[..].approx_eq(&self.frames),
+            ),
+            (BlockType::Notes, reloaded.notes[..].approx_eq(&self.notes)),
+            (BlockType::Walls, reloaded.walls[..].approx_eq(&self.walls)),
+            (
+                BlockType::Heights,
+                reloaded.heights[..].approx_eq(&self.heights),
+            ),
+            (
+                BlockType::Pauses,
+                reloaded.pauses[..].approx_eq(&self.pauses),
+            ),
+        ];
+
+        match blocks.iter().find(|(_, eq)| !eq) {
+            Some((bt, _)) => Err(BsorError::RoundTripMismatch(*bt)),
+            None => Ok(()),
+        }
+    }
+
+    /// Serializes the whole replay into a JSON document with camelCase keys
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Serializes the whole replay into a pretty-printed JSON document with camelCase keys
+    #[cfg(feature = "serde")]
+    pub fn to_json_pretty(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Load replay into memory using given options
+    ///
+    /// Returns the replay along with any non-fatal issues encountered (only reported in lenient mode, see [LoadOptions::lenient])
+    pub fn load_with_options<R: Read>(
+        r: &mut R,
+        options: &LoadOptions,
+    ) -> Result<(Replay, Vec<LoadWarning>)> {
+        let mut r = read_utils::PositionReader::new(r);
+
+        // looking ahead is needed only to recover lossy strings and detect missing cut info
+        if !options.lossy_strings && !options.detect_bad_notes_without_cut_info {
+            return Self::load_with_loaders(
+                &mut r,
+                options,
+                read_utils::PositionReader::pos,
+                Info::load_with_options,
+                Notes::load_with_options,
+            );
+        }
+
+        let load_info: InfoLoader<_> = if options.lossy_strings {
+            Info::load_lossy
+        } else {
+            Info::load_with_options
+        };
+        let load_notes: NotesLoader<_> = if options.detect_bad_notes_without_cut_info {
+            Notes::load_detecting_cut_info
+        } else {
+            Notes::load_with_options
+        };
+
+        Self::load_with_loaders(
+            &mut read_utils::PeekReader::new(r),
+            options,
+            read_utils::PeekReader::pos,
+            load_info,
+            load_notes,
+        )
+    }
+
+    fn load_with_loaders<R: Read>(
+        r: &mut R,
+        options: &LoadOptions,
+        pos: fn(&R) -> u64,
+        load_info: InfoLoader<R>,
+        load_notes: NotesLoader<R>,
+    ) -> Result<(Replay, Vec<LoadWarning>)> {
+        let mut warnings = Vec::new();
+
+        let header = Header::load(r)?;
+        let info = load_info(r, options)?;
+        let frames = Frames::load_with_options(r, pos(r), options, &mut warnings)?;
+        let notes = load_notes(r, pos(r), options, &mut warnings)?;
+        let walls = Walls::load_with_options(r, pos(r), options, &mut warnings)?;
+        let heights = Heights::load_with_options(r, pos(r), options, &mut warnings)?;
+        let pauses = Pauses::load_with_options(r, pos(r), options, &mut warnings)?;
+
+        Ok((
+            Replay {
+                version: header.version,
+                info,
+                frames,
+                notes,
+                walls,
+                heights,
+                pauses,
+            },
+            warnings,
+        ))
+    }
+
+    /// Load replay into memory, recording the start position of each block in the stream
+    pub fn load_with_offsets<RS: Read + Seek>(r: &mut RS) -> Result<(Replay, BlockOffsets)> {
         let header = Header::load(r)?;
+
+        let info_pos = r.stream_position()?;
         let info = Info::load(r)?;
+
+        let frames_pos = r.stream_position()?;
         let frames = Frames::load(r)?;
+
+        let notes_pos = r.stream_position()?;
         let notes = Notes::load(r)?;
+
+        let walls_pos = r.stream_position()?;
         let walls = Walls::load(r)?;
+
+        let heights_pos = r.stream_position()?;
         let heights = Heights::load(r)?;
+
+        let pauses_pos = r.stream_position()?;
         let pauses = Pauses::load(r)?;
 
-        Ok(Replay {
-            version: header.version,
-            info,
-            frames,
-            notes,
-            walls,
-            heights,
-            pauses,
+        Ok((
+            Replay {
+                version: header.version,
+                info,
+                frames,
+                notes,
+                walls,
+                heights,
+                pauses,
+            },
+            BlockOffsets {
+                info: info_pos,
+                frames: frames_pos,
+                notes: notes_pos,
+                walls: walls_pos,
+                heights: heights_pos,
+                pauses: pauses_pos,
+            },
+        ))
+    }
+
+    /// Returns accuracy of the replay, i.e. the score stored in [Info] divided by [Notes::max_possible_score()], or `None` if the run is incomplete (see [Info::is_complete()]) or there are no scoring notes
+    ///
+    /// [ReplayIndex] does not load notes, so there load the Notes block first and divide [Info::score] by [Notes::max_possible_score()] yourself
+    pub fn accuracy(&self) -> Option<f32> {
+        if !self.info.is_complete() {
+            return None;
+        }
+
+        let max_score = self.notes.max_possible_score();
+        if max_score == 0 {
+            return None;
+        }
+
+        Some(self.info.score as f32 / max_score as f32)
+    }
+
+    /// Returns scoring events (good cuts, bad cuts and misses) in event time order, each with its score and the combo and multiplier after the event
+    ///
+    /// Bombs are not returned, but they break the combo and decrease the multiplier. Notes not counting toward the score are skipped
+    pub fn scoring_events(&self) -> Vec<ScoringEvent> {
+        let mut order: Vec<usize> = (0..self.notes.len()).collect();
+        order.sort_by(|a, b| {
+            self.notes[*a]
+                .event_time
+                .total_cmp(&self.notes[*b].event_time)
+        });
+
+        let mut multiplier = ScoreMultiplier::new();
+        let mut combo = 0;
+        let mut total_score = 0;
+        let mut events = Vec::new();
+
+        for note_idx in order {
+            let note = &self.notes[note_idx];
+
+            if note.resets_combo() {
+                multiplier.decrease();
+                combo = 0;
+            } else if note.event_type == NoteEventType::Good && note.is_scoring() {
+                multiplier.increase();
+                combo += 1;
+            }
+
+            if !note.is_scoring() {
+                continue;
+            }
+
+            let score = note.cut_score().unwrap_or(0) * multiplier.value() as u32;
+            total_score += score;
+
+            events.push(ScoringEvent {
+                note_idx,
+                time: note.event_time,
+                event_type: note.event_type,
+                score,
+                total_score,
+                combo,
+                multiplier: multiplier.value(),
+            });
+        }
+
+        events
+    }
+
+    /// Returns accuracy curve resampled to `points` samples of (progress, accuracy), both in 0..1 range
+    ///
+    /// Accuracy at each scoring event is the score so far divided by the max score possible so far (both with the combo multiplier applied), progress is the event time relative to the first and the last scoring event. Samples are evenly spaced in progress, each taking the accuracy of the last event at or before it. Returns empty vec if there are no scoring events
+    pub fn normalized_accuracy_curve(&self, points: usize) -> Vec<(f32, f32)> {
+        let events = self.scoring_events();
+        let (Some(first), Some(last)) = (events.first(), events.last()) else {
+            return Vec::new();
+        };
+
+        let duration = last.time - first.time;
+        let mut multiplier = ScoreMultiplier::new();
+        let mut max_score = 0;
+        let timeline: Vec<(f32, f32)> = events
+            .iter()
+            .map(|e| {
+                multiplier.increase();
+                max_score += score::max_score(self.notes[e.note_idx].scoring_type)
+                    * multiplier.value() as u32;
+
+                let progress = if duration > 0.0 {
+                    (e.time - first.time) / duration
+                } else {
+                    1.0
+                };
+
+                (progress, e.total_score as f32 / max_score as f32)
+            })
+            .collect();
+
+        (0..points)
+            .map(|i| {
+                let progress = if points > 1 {
+                    i as f32 / (points - 1) as f32
+                } else {
+                    1.0
+                };
+                let idx = timeline.partition_point(|(p, _)| *p <= progress).max(1) - 1;
+
+                (progress, timeline[idx].1)
+            })
+            .collect()
+    }
+
+    /// Returns fraction of notes having a frame within [FRAME_COVERAGE_WINDOW] of their event time, low value indicates missing or short frame data
+    ///
+    /// Returns 0 if there are no notes
+    pub fn frame_coverage(&self) -> f32 {
+        if self.notes.is_empty() {
+            return 0.0;
+        }
+
+        let covered = self
+            .notes
+            .iter()
+            .filter(|n| {
+                self.frames
+                    .nearest_frame(SongTime(n.event_time))
+                    .is_some_and(|f| (f.time - n.event_time).abs() <= FRAME_COVERAGE_WINDOW)
+            })
+            .count();
+
+        covered as f32 / self.notes.len() as f32
+    }
+
+    /// Returns notes along with the frame closest to their event time, `None` if there are no frames
+    ///
+    /// Frames are expected to be sorted by time. Notes are matched walking the frames alongside them, so sorted notes are matched in linear time, unsorted ones just make the walk go back
+    pub fn notes_with_poses(&self) -> impl Iterator<Item = (&Note, Option<&Frame>)> {
+        let frames = &self.frames;
+        let mut idx = 0;
+
+        self.notes.iter().map(move |note| {
+            let time = note.event_time;
+
+            while idx + 1 < frames.len() && frames[idx + 1].time <= time {
+                idx += 1;
+            }
+            while idx > 0 && frames[idx].time > time {
+                idx -= 1;
+            }
+
+            let frame = frames.get(idx).map(|current| match frames.get(idx + 1) {
+                Some(next) if current.time <= time && next.time - time < time - current.time => {
+                    next
+                }
+                _ => current,
+            });
+
+            (note, frame)
         })
     }
+
+    /// Re-bases all times so that the earliest event (see [Replay::time_span()]) or pause is at zero, returning the subtracted offset
+    ///
+    /// Mutates the replay in place, shifts frame, note, wall, height and pause times as well as [Info::start_time] and [Info::fail_time] (if the run failed). The change can be reverted only by keeping the returned offset
+    pub fn normalize_times(&mut self) -> ReplayTime {
+        let offset = self
+            .event_times()
+            .chain(self.pauses.iter().map(|p| p.time))
+            .reduce(ReplayTime::min)
+            .unwrap_or(0.0);
+
+        self.frames.shift_times(offset);
+        self.notes.shift_times(offset);
+        self.walls.shift_times(offset);
+        self.heights.shift_times(offset);
+        self.pauses.shift_times(offset);
+
+        self.info.start_time -= offset;
+        if self.info.fail_time != 0.0 {
+            self.info.fail_time -= offset;
+        }
+
+        offset
+    }
+
+    /// Returns whether the replay was recorded in 360/90-degree mode
+    ///
+    /// Besides the mode name (which may be a custom string) the head rotation is checked: in Standard mode the player faces forward, so the total head yaw stays well within a half turn, while exceeding [ROTATING_MODE_YAW_THRESHOLD] means the player physically turned around
+    pub fn is_rotating_mode(&self) -> bool {
+        self.info.mode.contains("360Degree")
+            || self.info.mode.contains("90Degree")
+            || self.frames.total_head_yaw().abs() > ROTATING_MODE_YAW_THRESHOLD
+    }
+
+    /// Compares notes of this run with another run of the same map, reporting per-note score and accuracy deltas
+    ///
+    /// Notes are paired by id and spawn time using [Notes::match_to_map()], so both runs may have different note counts (e.g. when one of them failed). Result contains an entry for each note of this run (in order) followed by entries for notes present only in the other run
+    pub fn compare_runs(&self, other: &Replay) -> Vec<NoteComparison> {
+        let other_map = other
+            .notes
+            .iter()
+            .map(|n| (n.id(), n.spawn_time))
+            .collect::<Vec<_>>();
+
+        let scores = |note: Option<&Note>| {
+            note.and_then(|n| n.cut_score_components()).map_or(
+                (0, 0),
+                |(pre_swing, post_swing, accuracy)| {
+                    ((pre_swing + post_swing + accuracy) as i32, accuracy as i32)
+                },
+            )
+        };
+
+        self.notes
+            .match_to_map(&other_map)
+            .into_iter()
+            .map(|m| {
+                let (note_idx, other_idx) = match m {
+                    MatchResult::Matched {
+                        note_idx,
+                        expected_idx,
+                    } => (Some(note_idx), Some(expected_idx)),
+                    MatchResult::UnmatchedNote { note_idx } => (Some(note_idx), None),
+                    MatchResult::UnmatchedExpected { expected_idx } => (None, Some(expected_idx)),
+                };
+
+                let (score, accuracy) = scores(note_idx.map(|idx| &self.notes[idx]));
+                let (other_score, other_accuracy) = scores(other_idx.map(|idx| &other.notes[idx]));
+
+                NoteComparison {
+                    note_idx,
+                    other_idx,
+                    score_delta: other_score - score,
+                    accuracy_delta: other_accuracy - accuracy,
+                }
+            })
+            .collect()
+    }
+
+    /// Flattens notes into a struct-of-arrays layout, see [ReplayColumns]
+    pub fn to_columns(&self) -> ReplayColumns {
+        ReplayColumns::from_notes(&self.notes)
+    }
+
+    /// Returns score with modifiers applied, as ranked by BeatLeader
+    ///
+    /// [Info::score] is multiplied by [Info::score_multiplier()] in f32 and truncated towards zero (the C# `int` cast BeatLeader uses), so it is 0 if a disqualifying modifier is present
+    pub fn modified_score(&self) -> i32 {
+        (self.info.score as f32 * self.info.score_multiplier()) as i32
+    }
+
+    /// Returns fraction of notes reached before the run ended, i.e. with event time not later than [Info::fail_time] for failed runs or the last frame time for abandoned runs (see [Info::is_complete()])
+    ///
+    /// Returns 1 for cleared runs and 0 for not cleared runs without notes
+    pub fn completion_ratio(&self) -> f32 {
+        let end = if self.info.fail_time > 0.0 {
+            self.info.fail_time
+        } else if !self.info.is_complete() {
+            self.frames.last().map_or(0.0, |f| f.time)
+        } else {
+            return 1.0;
+        };
+
+        if self.notes.is_empty() {
+            return 0.0;
+        }
+
+        let reached = self.notes.iter().filter(|n| n.event_time <= end).count();
+
+        reached as f32 / self.notes.len() as f32
+    }
+
+    /// Returns min and max time seen across frames, notes, walls and heights
+    ///
+    /// Empty blocks are skipped, returns (0, 0) if all of them are empty
+    pub fn time_span(&self) -> (ReplayTime, ReplayTime) {
+        self.event_times()
+            .fold(None, |span, time| match span {
+                None => Some((time, time)),
+                Some((min, max)) => Some((time.min(min), time.max(max))),
+            })
+            .unwrap_or((0.0, 0.0))
+    }
+
+    fn event_times(&self) -> impl Iterator<Item = ReplayTime> + '_ {
+        self.frames
+            .iter()
+            .map(|f| f.time)
+            .chain(self.notes.iter().map(|n| n.event_time))
+            .chain(self.walls.iter().map(|w| w.time))
+            .chain(self.heights.iter().map(|h| h.time))
+    }
+
+    /// Returns the time of the last frame minus [Pauses::total_duration()], clamped at 0
+    ///
+    /// Pause durations are stored in whole wall-clock seconds and are subtracted as seconds of song time as is, i.e. assuming the song was played at normal speed
+    pub fn active_duration(&self) -> ReplayTime {
+        let last_frame_time = self.frames.last().map_or(0.0, |f| f.time);
+
+        (last_frame_time - self.pauses.total_duration() as ReplayTime).max(0.0)
+    }
+
+    /// Reconstructs player energy after each note event and wall hit, as (time, energy) pairs sorted by time
+    ///
+    /// Notes change the energy by a fixed amount depending on the event type, walls carry the energy left after the hit
+    pub fn energy_curve(&self) -> Vec<(ReplayTime, ReplayFloat)> {
+        enum EnergyEvent {
+            Change(ReplayFloat),
+            Set(ReplayFloat),
+        }
+
+        let mut events = self
+            .notes
+            .iter()
+            .map(|n| {
+                (
+                    n.event_time,
+                    EnergyEvent::Change(score::energy_change(n.event_type)),
+                )
+            })
+            .chain(
+                self.walls
+                    .iter()
+                    .map(|w| (w.time, EnergyEvent::Set(w.energy))),
+            )
+            .collect::<Vec<_>>();
+        events.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut energy = INITIAL_ENERGY;
+
+        events
+            .into_iter()
+            .map(|(time, event)| {
+                energy = match event {
+                    EnergyEvent::Change(change) => energy + change,
+                    EnergyEvent::Set(value) => value,
+                }
+                .clamp(0.0, 1.0);
+
+                (time, energy)
+            })
+            .collect()
+    }
+
+    /// Downsamples [Replay::energy_curve()] to energy at the end of each whole second, from second 0 up to the end of [Replay::time_span()]
+    pub fn energy_per_second(&self) -> Vec<(u32, f32)> {
+        let curve = self.energy_curve();
+        let (_, end) = self.time_span();
+
+        let mut energy = INITIAL_ENERGY;
+        let mut points = curve.iter().peekable();
+
+        (0..=end.max(0.0).floor() as u32)
+            .map(|second| {
+                while let Some((_, value)) =
+                    points.next_if(|(time, _)| *time < (second + 1) as ReplayTime)
+                {
+                    energy = *value;
+                }
+
+                (second, energy)
+            })
+            .collect()
+    }
+
+    /// Returns whether the replay was played with No Fail and the player would have failed, i.e. energy dropped to zero
+    pub fn no_fail_triggered(&self) -> bool {
+        self.info.modifiers_parsed().contains(&Modifier::NoFail)
+            && self.energy_curve().iter().any(|(_, energy)| *energy <= 0.0)
+    }
+
+    /// Cheaply verifies that the blocks of a replay tile the stream up to its end, without parsing their contents
+    ///
+    /// Reads the header and for each block only its id and items count (Notes have variable size, so they are walked through to get the block size), then seeks past it. Returns [BsorError::Io] if the stream is shorter than the blocks require and [BsorError::InvalidBsor] if there is data left after the last block
+    pub fn verify_structure<RS: Read + Seek>(r: &mut RS) -> Result<()> {
+        Header::load(r)?;
+        Info::skip(r)?;
+
+        let mut pos = r.stream_position()?;
+        pos += Frames::load_real_block_size(r, pos)?.bytes;
+
+        r.seek(SeekFrom::Start(pos))?;
+        pos += Notes::load_real_block_size(r, pos)?.bytes;
+
+        r.seek(SeekFrom::Start(pos))?;
+        pos += Walls::load_real_block_size(r, pos)?.bytes;
+
+        r.seek(SeekFrom::Start(pos))?;
+        pos += Heights::load_real_block_size(r, pos)?.bytes;
+
+        r.seek(SeekFrom::Start(pos))?;
+        pos += Pauses::load_real_block_size(r, pos)?.bytes;
+
+        let end = r.seek(SeekFrom::End(0))?;
+        if pos > end {
+            return Err(BsorError::Io(ErrorKind::UnexpectedEof.into()));
+        }
+        if pos < end {
+            return Err(BsorError::InvalidBsor);
+        }
+
+        Ok(())
+    }
+
+    /// Returns block name and index of each item having a negative time, event time or spawn time, which indicates a corrupted replay
+    pub fn negative_time_events(&self) -> Vec<(&'static str, usize)> {
+        fn find<T>(
+            bt: BlockType,
+            items: &[T],
+            is_negative: fn(&T) -> bool,
+        ) -> Vec<(&'static str, usize)> {
+            items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| is_negative(item))
+                .map(|(idx, _)| (bt.name(), idx))
+                .collect()
+        }
+
+        [
+            find(BlockType::Frames, &self.frames, |f| f.time < 0.0),
+            find(BlockType::Notes, &self.notes, |n| {
+                n.event_time < 0.0 || n.spawn_time < 0.0
+            }),
+            find(BlockType::Walls, &self.walls, |w| {
+                w.time < 0.0 || w.spawn_time < 0.0
+            }),
+            find(BlockType::Heights, &self.heights, |h| h.time < 0.0),
+            find(BlockType::Pauses, &self.pauses, |p| p.time < 0.0),
+        ]
+        .concat()
+    }
+
+    /// Checks that Good and Bad notes (and only them) carry cut info, returning indices of the notes violating it
+    pub fn validate_cut_info_invariants(&self) -> std::result::Result<(), Vec<usize>> {
+        let invalid = self
+            .notes
+            .iter()
+            .enumerate()
+            .filter(|(_, note)| !note.has_valid_cut_info())
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid)
+        }
+    }
+
+    /// Checks invariants real replays sometimes violate, returning all violations found as [ValidationWarning]s
+    pub fn validate(&self) -> Result<Vec<ValidationWarning>> {
+        let mut warnings = Vec::new();
+
+        let mut check_sorted = |bt: BlockType, times: Vec<ReplayTime>| {
+            warnings.extend(
+                times
+                    .windows(2)
+                    .enumerate()
+                    .filter(|(_, w)| w[1] < w[0])
+                    .map(|(idx, _)| ValidationWarning::UnsortedItem {
+                        block: bt.name(),
+                        idx: idx + 1,
+                    }),
+            );
+        };
+        check_sorted(
+            BlockType::Frames,
+            self.frames.iter().map(|f| f.time).collect(),
+        );
+        check_sorted(
+            BlockType::Notes,
+            self.notes.iter().map(|n| n.event_time).collect(),
+        );
+
+        warnings.extend(
+            self.frames
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| f.fps <= 0)
+                .map(|(idx, f)| ValidationWarning::InvalidFps { idx, fps: f.fps }),
+        );
+
+        let (start, end) = self.time_span();
+        warnings.extend(
+            self.pauses
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.time < start || p.time > end)
+                .map(|(idx, p)| ValidationWarning::PauseOutOfRange { idx, time: p.time }),
+        );
+
+        warnings.extend(
+            self.notes
+                .iter()
+                .enumerate()
+                .filter(|(_, n)| n.line_idx >= GRID_LINES || n.line_layer >= GRID_LAYERS)
+                .map(|(idx, n)| ValidationWarning::NoteOutOfGrid {
+                    idx,
+                    line_idx: n.line_idx,
+                    line_layer: n.line_layer,
+                }),
+        );
+
+        if let Err(invalid) = self.validate_cut_info_invariants() {
+            warnings.extend(
+                invalid
+                    .into_iter()
+                    .map(|idx| ValidationWarning::InvalidCutInfo { idx }),
+            );
+        }
+
+        warnings.extend(
+            self.negative_time_events()
+                .into_iter()
+                .map(|(block, idx)| ValidationWarning::NegativeTime { block, idx }),
+        );
+
+        Ok(warnings)
+    }
+}
+
+/// Max distance in seconds between a note event and a frame for the note to be covered by frames, see [Replay::frame_coverage()]
+pub const FRAME_COVERAGE_WINDOW: ReplayTime = 0.1;
+
+/// Total head yaw in radians above which a replay is treated as recorded in a rotating mode, see [Replay::is_rotating_mode()]
+pub const ROTATING_MODE_YAW_THRESHOLD: ReplayFloat = std::f32::consts::PI;
+
+/// Number of lines of the standard note grid, see [Replay::validate()]
+pub const GRID_LINES: LineIdx = 4;
+
+/// Number of layers of the standard note grid, see [Replay::validate()]
+pub const GRID_LAYERS: LineLayer = 3;
+
+/// Default max number of items declared in a single block, see [LoadOptions::max_block_items]
+pub const DEFAULT_MAX_BLOCK_ITEMS: usize = 16 * 1024 * 1024;
+
+/// Max number of items space is reserved for up front, larger blocks grow while being read
+const MAX_PREALLOCATED_ITEMS: usize = 64 * 1024;
+
+/// Loader of the Info block used by [Replay::load_with_options()]
+type InfoLoader<R> = fn(&mut R, &LoadOptions) -> Result<Info>;
+
+/// Loader of the Notes block used by [Replay::load_with_options()], taking the stream offset of the block
+type NotesLoader<R> = fn(&mut R, u64, &LoadOptions, &mut Vec<LoadWarning>) -> Result<Notes>;
+
+/// Options used by [Replay::load_with_options()], start from [LoadOptions::default()] and set the fields needed
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct LoadOptions {
+    /// Salvage partially corrupted replays: when the stream ends in the middle of a block, keep the items loaded so far and treat all subsequent blocks as empty instead of failing, reporting it as [LoadWarning]
+    pub lenient: bool,
+    /// Decode Info strings lossily instead of failing on invalid UTF-8, also recovering strings whose length was saved in chars instead of bytes by very old versions of the BL mod
+    pub lossy_strings: bool,
+    /// Max length in bytes of any Info string, longer strings are treated as a corrupted replay ([BsorError::InvalidBsor])
+    pub max_string_len: usize,
+    /// Max number of items declared in a single block, larger counts are treated as a corrupted replay ([BsorError::InvalidBsor])
+    pub max_block_items: usize,
+    /// Tell Bad notes without cut info, as written by some game versions, apart by looking ahead at the following bytes
+    ///
+    /// The bytes are checked to be long enough for cut info and to hold valid bools and saber type, otherwise the note is read without it. As this is only a guess, it is off by default and Bad notes are always read with cut info
+    pub detect_bad_notes_without_cut_info: bool,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            lenient: false,
+            lossy_strings: false,
+            max_string_len: MAX_INFO_STRING_LEN,
+            max_block_items: DEFAULT_MAX_BLOCK_ITEMS,
+            detect_bad_notes_without_cut_info: false,
+        }
+    }
+}
+
+/// Non-fatal issue encountered while loading a replay in lenient mode
+#[derive(Debug, PartialEq)]
+pub enum LoadWarning {
+    /// Stream ended before all items declared in the block could be read
+    TruncatedBlock {
+        block: &'static str,
+        declared: ReplayInt,
+        loaded: ReplayInt,
+    },
+    /// Stream ended before the start of the block
+    MissingBlock { block: &'static str },
+}
+
+impl fmt::Display for LoadWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadWarning::TruncatedBlock {
+                block,
+                declared,
+                loaded,
+            } => write!(
+                f,
+                "{} block truncated, loaded {} of {} items",
+                block, loaded, declared
+            ),
+            LoadWarning::MissingBlock { block } => write!(f, "{} block is missing", block),
+        }
+    }
+}
+
+/// Suspicious, but non-fatal issue found in a loaded replay, see [Replay::validate()]
+#[derive(Debug, PartialEq)]
+pub enum ValidationWarning {
+    /// Item time is lower than the time of the preceding item in the block
+    UnsortedItem { block: &'static str, idx: usize },
+    /// Frame FPS is not positive
+    InvalidFps { idx: usize, fps: ReplayInt },
+    /// Pause time is outside of [Replay::time_span()]
+    PauseOutOfRange { idx: usize, time: ReplayTime },
+    /// Note is placed outside of the standard grid of [GRID_LINES] lines and [GRID_LAYERS] layers
+    NoteOutOfGrid {
+        idx: usize,
+        line_idx: LineIdx,
+        line_layer: LineLayer,
+    },
+    /// Note carries cut info although it is not a Good or Bad one, or the other way round, see [Replay::validate_cut_info_invariants()]
+    InvalidCutInfo { idx: usize },
+    /// Item has a negative time, see [Replay::negative_time_events()]
+    NegativeTime { block: &'static str, idx: usize },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationWarning::UnsortedItem { block, idx } => {
+                write!(f, "{} block item {} is out of order", block, idx)
+            }
+            ValidationWarning::InvalidFps { idx, fps } => {
+                write!(f, "frame {} has invalid fps {}", idx, fps)
+            }
+            ValidationWarning::PauseOutOfRange { idx, time } => {
+                write!(f, "pause {} at {} is outside of the replay", idx, time)
+            }
+            ValidationWarning::NoteOutOfGrid {
+                idx,
+                line_idx,
+                line_layer,
+            } => write!(
+                f,
+                "note {} is outside of the grid (line {}, layer {})",
+                idx, line_idx, line_layer
+            ),
+            ValidationWarning::InvalidCutInfo { idx } => {
+                write!(f, "note {} has cut info not matching its event type", idx)
+            }
+            ValidationWarning::NegativeTime { block, idx } => {
+                write!(f, "{} block item {} has negative time", block, idx)
+            }
+        }
+    }
+}
+
+/// Start position of each block in the stream, see [Replay::load_with_offsets()]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockOffsets {
+    pub info: u64,
+    pub frames: u64,
+    pub notes: u64,
+    pub walls: u64,
+    pub heights: u64,
+    pub pauses: u64,
+}
+
+/// Single scoring event, see [Replay::scoring_events()]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoringEvent {
+    /// Index of the note in the Notes block
+    pub note_idx: usize,
+    /// Note event time, in seconds
+    pub time: ReplayTime,
+    pub event_type: NoteEventType,
+    /// Cut score multiplied by the current multiplier, 0 for bad cuts and misses
+    pub score: u32,
+    /// Sum of scores of all events so far, including this one
+    pub total_score: u32,
+    /// Combo after the event
+    pub combo: u32,
+    /// Multiplier after the event
+    pub multiplier: u8,
 }
 
 /// Replay index needed to load individual blocks
@@ -132,9 +1093,28 @@ pub struct ReplayIndex {
     pub walls: BlockIndex<Walls>,
     pub heights: BlockIndex<Heights>,
     pub pauses: BlockIndex<Pauses>,
+    cache: [OnceLock<LoadedBlock>; 6],
+}
+
+/// Block loaded by [ReplayIndex::get_or_load()]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadedBlock {
+    Info(Box<Info>),
+    Frames(Frames),
+    Notes(Notes),
+    Walls(Walls),
+    Heights(Heights),
+    Pauses(Pauses),
 }
 
 impl ReplayIndex {
+    /// Opens the file at given path and indexes it, see [ReplayIndex::index()]
+    ///
+    /// The file is closed afterwards, blocks are loaded from a reader opened by the caller
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<ReplayIndex> {
+        Self::index(&mut BufReader::new(File::open(path)?))
+    }
+
     /// Indexes replay, so you can easily load each block individually
     pub fn index<RS: Read + Seek>(r: &mut RS) -> Result<ReplayIndex> {
         let header = Header::load(r)?;
@@ -168,18 +1148,92 @@ impl ReplayIndex {
             walls,
             heights,
             pauses,
+            cache: Default::default(),
         })
     }
+
+    /// Loads given block, caching it so that each block is parsed at most once for the lifetime of the index
+    ///
+    /// ```no_run
+    /// use bsor::prelude::*;
+    /// use bsor::replay::BlockType;
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    ///
+    /// let br = &mut BufReader::new(File::open("example.bsor").unwrap());
+    /// let replay_index = ReplayIndex::index(br).unwrap();
+    ///
+    /// let block = replay_index.get_or_load(br, BlockType::Notes).unwrap();
+    /// if let LoadedBlock::Notes(notes) = block {
+    ///     println!("Notes count: {}", notes.len());
+    /// }
+    /// ```
+    pub fn get_or_load<RS: Read + Seek>(&self, r: &mut RS, bt: BlockType) -> Result<&LoadedBlock> {
+        let cell = &self.cache[bt as usize];
+        if let Some(block) = cell.get() {
+            return Ok(block);
+        }
+
+        let block = match bt {
+            BlockType::Info => LoadedBlock::Info(Box::new(self.info.clone())),
+            BlockType::Frames => LoadedBlock::Frames(self.frames.load(r)?),
+            BlockType::Notes => LoadedBlock::Notes(self.notes.load(r)?),
+            BlockType::Walls => LoadedBlock::Walls(self.walls.load(r)?),
+            BlockType::Heights => LoadedBlock::Heights(self.heights.load(r)?),
+            BlockType::Pauses => LoadedBlock::Pauses(self.pauses.load(r)?),
+        };
+
+        Ok(cell.get_or_init(|| block))
+    }
+
+    /// Indexes replay from a reader that is not [std::io::Seek] by reading the whole stream into memory once
+    ///
+    /// Returns the index along with the buffer, blocks can be loaded from it using [std::io::Cursor]
+    /// ```no_run
+    /// use bsor::prelude::*;
+    /// use std::io::Cursor;
+    ///
+    /// # let mut stream = std::io::empty();
+    /// let (replay_index, buf) = ReplayIndex::index_buffered(&mut stream).unwrap();
+    /// let notes = replay_index.notes.load(&mut Cursor::new(&buf)).unwrap();
+    /// ```
+    pub fn index_buffered<R: Read>(r: &mut R) -> Result<(ReplayIndex, Vec<u8>)> {
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf)?;
+
+        let index = Self::index(&mut io::Cursor::new(&buf))?;
+
+        Ok((index, buf))
+    }
 }
 
+/// Former name of [ReplayIndex], kept for downstream code written against older versions
+///
+/// ```no_run
+/// # #![allow(deprecated)]
+/// use bsor::replay::{LoadBlock, ParsedReplay};
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// let br = &mut BufReader::new(File::open("example.bsor").unwrap());
+/// let parsed_replay: ParsedReplay = ParsedReplay::index(br).unwrap();
+/// let notes = parsed_replay.notes.load(br).unwrap();
+/// ```
+#[deprecated(note = "use ReplayIndex instead")]
+pub type ParsedReplay = ReplayIndex;
+
+/// Former name of [BlockIndex], kept for downstream code written against older versions
+#[deprecated(note = "use BlockIndex instead")]
+pub type ParsedReplayBlock<T> = BlockIndex<T>;
+
 /// Struct storing index data about each block
 #[derive(Debug)]
 pub struct BlockIndex<T> {
-    ///! position in stream
+    /// position in stream
     pos: u64,
-    ///! block length in bytes
+    /// block length in bytes
     bytes: u64,
-    ///! sub items count
+    /// sub items count
     items_count: i32,
     _phantom: PhantomData<T>,
 }
@@ -204,6 +1258,55 @@ impl<T> BlockIndex<T> {
     pub fn is_empty(&self) -> bool {
         self.items_count == 0
     }
+
+    /// Returns position in the stream right after the block, i.e. the start of the next block
+    ///
+    /// Can be stored as a checkpoint and passed later to `resume_from()` of the next block index
+    /// ```no_run
+    /// use bsor::prelude::*;
+    /// use std::io::Cursor;
+    ///
+    /// # let buf = Vec::new();
+    /// let mut r = Cursor::new(&buf);
+    /// let replay_index = ReplayIndex::index(&mut r).unwrap();
+    /// let checkpoint = replay_index.notes.end_pos();
+    ///
+    /// // ...later on
+    /// let walls_index = BlockIndex::<Walls>::resume_from(&mut r, checkpoint).unwrap();
+    /// let walls = walls_index.load(&mut r).unwrap();
+    /// ```
+    pub fn end_pos(&self) -> u64 {
+        self.pos + self.bytes
+    }
+}
+
+/// Writes the bsor file header (magic number and version), to be followed by the Info block and all the other blocks in order
+pub fn write_header<W: Write>(w: &mut W, version: u8) -> Result<()> {
+    Header { version }.write(w)
+}
+
+/// Returns static size in bytes of each block header, i.e. the block id (and items count for all blocks but Info)
+pub fn block_static_sizes() -> [(BlockType, usize); 6] {
+    [
+        (BlockType::Info, size_of::<u8>()),
+        (BlockType::Frames, Frames::get_static_size()),
+        (BlockType::Notes, Notes::get_static_size()),
+        (BlockType::Walls, Walls::get_static_size()),
+        (BlockType::Heights, Heights::get_static_size()),
+        (BlockType::Pauses, Pauses::get_static_size()),
+    ]
+}
+
+/// Returns size in bytes of a single item of each block, or `None` if it is not fixed (Info strings and Notes, which carry cut info only for good and bad cuts)
+pub fn item_static_sizes() -> [(BlockType, Option<usize>); 6] {
+    [
+        (BlockType::Info, None),
+        (BlockType::Frames, Some(Frame::get_static_size())),
+        (BlockType::Notes, None),
+        (BlockType::Walls, Some(Wall::get_static_size())),
+        (BlockType::Heights, Some(Height::get_static_size())),
+        (BlockType::Pauses, Some(Pause::get_static_size())),
+    ]
 }
 
 trait GetStaticBlockSize {
@@ -235,7 +1338,9 @@ pub trait LoadBlock {
     fn load<RS: Read + Seek>(&self, r: &mut RS) -> Result<Self::Item>;
 }
 
-pub(crate) enum BlockType {
+/// Type of the replay block, discriminant values are the block ids stored in the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockType {
     Info = 0,
     Frames,
     Notes,
@@ -244,31 +1349,171 @@ pub(crate) enum BlockType {
     Pauses,
 }
 
-impl TryInto<u8> for BlockType {
-    type Error = BsorError;
-
-    fn try_into(self) -> std::result::Result<u8, Self::Error> {
-        Ok(self as u8)
+impl BlockType {
+    pub(crate) fn from_id(id: u8) -> Option<BlockType> {
+        match id {
+            0 => Some(BlockType::Info),
+            1 => Some(BlockType::Frames),
+            2 => Some(BlockType::Notes),
+            3 => Some(BlockType::Walls),
+            4 => Some(BlockType::Heights),
+            5 => Some(BlockType::Pauses),
+            _ => None,
+        }
     }
-}
 
-fn assert_start_of_block<R: Read>(r: &mut R, bt: BlockType) -> Result<()> {
-    match read_utils::read_byte(r) {
-        Ok(v) => {
-            if v != bt.try_into()? {
-                Err(BsorError::InvalidBsor)
-            } else {
-                Ok(())
-            }
+    /// Returns block name
+    pub fn name(&self) -> &'static str {
+        match self {
+            BlockType::Info => "Info",
+            BlockType::Frames => "Frames",
+            BlockType::Notes => "Notes",
+            BlockType::Walls => "Walls",
+            BlockType::Heights => "Heights",
+            BlockType::Pauses => "Pauses",
         }
-        Err(e) => Err(e),
     }
 }
 
-#[cfg(test)]
+impl TryFrom<u8> for BlockType {
+    type Error = BsorError;
+
+    /// Returns [BsorError::InvalidBsor] for an unknown block id
+    fn try_from(id: u8) -> std::result::Result<Self, Self::Error> {
+        BlockType::from_id(id).ok_or(BsorError::InvalidBsor)
+    }
+}
+
+impl TryInto<u8> for BlockType {
+    type Error = BsorError;
+
+    fn try_into(self) -> std::result::Result<u8, Self::Error> {
+        Ok(self as u8)
+    }
+}
+
+/// Reads block id, returning [BsorError::UnexpectedBlock] with both block ids and the stream `offset` of the block if it is not the expected one
+fn expect_block<R: Read>(r: &mut R, bt: BlockType, offset: u64) -> Result<()> {
+    let expected = bt.try_into()?;
+    let found = read_utils::read_byte(r)?;
+
+    if found != expected {
+        return Err(BsorError::UnexpectedBlock {
+            expected,
+            found,
+            offset,
+        });
+    }
+
+    Ok(())
+}
+
+/// Max difference of floats still considered equal by [Replay::assert_roundtrip()]
+pub const ROUNDTRIP_EPSILON: ReplayFloat = 1e-6;
+
+/// Compares values field by field, floats with [ROUNDTRIP_EPSILON] tolerance and NaN floats as equal
+pub(crate) trait ApproxEq {
+    fn approx_eq(&self, other: &Self) -> bool;
+}
+
+impl ApproxEq for ReplayFloat {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self == other
+            || (self.is_nan() && other.is_nan())
+            || (self - other).abs() <= ROUNDTRIP_EPSILON
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Option<T> {
+    fn approx_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.approx_eq(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.approx_eq(b))
+    }
+}
+
+/// Loads block id, items count and all block items, in lenient mode stopping at the end of the stream
+fn load_block_items<R: Read, T>(
+    r: &mut R,
+    bt: BlockType,
+    offset: u64,
+    options: &LoadOptions,
+    warnings: &mut Vec<LoadWarning>,
+    mut load_item: impl FnMut(&mut R) -> Result<T>,
+) -> Result<Vec<T>> {
+    let block = bt.name();
+
+    let count = match expect_block(r, bt, offset).and_then(|_| read_utils::read_int(r)) {
+        Ok(count) => count,
+        Err(e) if options.lenient && is_unexpected_eof(&e) => {
+            warnings.push(LoadWarning::MissingBlock { block });
+            return Ok(Vec::new());
+        }
+        Err(e) => return Err(e),
+    };
+
+    if count < 0 || count as usize > options.max_block_items {
+        return Err(BsorError::InvalidBsor);
+    }
+
+    let mut vec = Vec::<T>::with_capacity((count as usize).min(MAX_PREALLOCATED_ITEMS));
+
+    for _ in 0..count {
+        match load_item(r) {
+            Ok(item) => vec.push(item),
+            Err(e) if options.lenient && is_unexpected_eof(&e) => {
+                warnings.push(LoadWarning::TruncatedBlock {
+                    block,
+                    declared: count,
+                    loaded: vec.len() as ReplayInt,
+                });
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(vec)
+}
+
+/// Writes block id, items count and all block items
+fn write_block_items<W: Write, T>(
+    w: &mut W,
+    bt: BlockType,
+    items: &[T],
+    write_item: fn(&T, &mut W) -> Result<()>,
+) -> Result<()> {
+    write_utils::write_byte(w, bt.try_into()?)?;
+    write_utils::write_int(w, items.len() as ReplayInt)?;
+
+    for item in items {
+        write_item(item, w)?;
+    }
+
+    Ok(())
+}
+
+fn is_unexpected_eof(e: &BsorError) -> bool {
+    matches!(e, BsorError::Io(e) if e.kind() == ErrorKind::UnexpectedEof)
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests_util::{generate_random_replay, get_replay_buffer};
+    use crate::replay::note::{ColorType, NoteEventType, NoteScoringType};
+    use crate::tests_util::{
+        generate_bomb_with_id, generate_good_note_with_ratings, generate_note_at,
+        generate_random_frame, generate_random_note, generate_random_note_cut_info,
+        generate_random_replay, generate_random_wall, get_replay_buffer,
+    };
     use std::io::Cursor;
 
     #[test]
@@ -290,6 +1535,530 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn it_returns_io_error_when_frames_block_is_truncated() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let mut buf = get_replay_buffer(&replay)?;
+        let frames_pos = ReplayIndex::index(&mut Cursor::new(&buf))?.frames.pos() as usize;
+        buf.truncate(frames_pos + 5 + 92 + 10);
+
+        let result = Replay::load(&mut Cursor::new(buf));
+
+        assert!(matches!(result, Err(BsorError::Io(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_read_header() -> Result<()> {
+        let mut buf = BSOR_MAGIC.to_le_bytes().to_vec();
+        buf.push(1);
+
+        assert_eq!(Replay::read_header(&mut Cursor::new(&buf))?, 1);
+
+        buf[0] ^= 0xff;
+        assert!(matches!(
+            Replay::read_header(&mut Cursor::new(&buf)),
+            Err(BsorError::InvalidBsor)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_check_bsor_magic() -> Result<()> {
+        let buf = get_replay_buffer(&generate_random_replay())?;
+        assert!(Replay::is_bsor(&mut Cursor::new(&buf)));
+
+        let mut cursor = Cursor::new(&buf);
+        Replay::is_bsor(&mut cursor);
+        assert_eq!(cursor.position(), 4);
+
+        let mut invalid = buf.clone();
+        invalid[3] ^= 0xff;
+        assert!(!Replay::is_bsor(&mut Cursor::new(&invalid)));
+        assert!(!Replay::is_bsor(&mut Cursor::new(&buf[..3])));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_load_replay_and_index_from_path() -> Result<()> {
+        let replay = generate_random_replay();
+        let buf = get_replay_buffer(&replay)?;
+        let path = std::env::temp_dir().join(format!("bsor-from-path-{}.bsor", std::process::id()));
+        std::fs::write(&path, &buf)?;
+
+        let loaded = Replay::from_path(&path);
+        let index = ReplayIndex::from_path(&path);
+        std::fs::remove_file(&path)?;
+
+        let loaded = loaded?;
+        assert_eq!(loaded.info, replay.info);
+        assert_eq!(loaded.notes, replay.notes);
+        assert_eq!(index?.notes.len(), replay.notes.len() as i32);
+
+        assert!(matches!(Replay::from_path(&path), Err(BsorError::Io(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_load_replay_from_slice() -> Result<()> {
+        let buf = get_replay_buffer(&generate_random_replay())?;
+
+        let result = Replay::from_slice(&buf)?;
+        let expected = Replay::load(&mut Cursor::new(&buf))?;
+
+        assert_eq!(result.version, expected.version);
+        assert_eq!(result.info, expected.info);
+        assert_eq!(result.frames, expected.frames);
+        assert_eq!(result.notes, expected.notes);
+        assert_eq!(result.walls, expected.walls);
+        assert_eq!(result.heights, expected.heights);
+        assert_eq!(result.pauses, expected.pauses);
+
+        assert!(matches!(
+            Replay::from_slice(&buf[..buf.len() - 1]),
+            Err(BsorError::Io(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_load_replay_with_bytes() -> Result<()> {
+        let mut replay = generate_random_replay();
+        let mut notes = replay.notes.to_vec();
+        notes.push(generate_bomb_with_id(1999));
+        replay.notes = Notes::new(notes);
+        let buf = get_replay_buffer(&replay)?;
+
+        let (replay, bytes) = Replay::load_with_bytes(&mut Cursor::new(buf.clone()))?;
+
+        assert_eq!(bytes, buf);
+        assert_eq!(get_replay_buffer(&replay)?, bytes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_assemble_replay_from_individual_blocks() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let mut buf = Vec::new();
+        write_header(&mut buf, replay.version)?;
+        replay.info.write(&mut buf)?;
+        replay.frames.write(&mut buf)?;
+        replay.notes.write(&mut buf)?;
+        replay.walls.write(&mut buf)?;
+        replay.heights.write(&mut buf)?;
+        replay.pauses.write(&mut buf)?;
+
+        assert_eq!(buf, get_replay_buffer(&replay)?);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_can_round_trip_replay_through_json() -> Result<()> {
+        let mut replay = generate_random_replay();
+        let mut notes = replay.notes.to_vec();
+        notes.push(generate_bomb_with_id(1999));
+        replay.notes = Notes::new(notes);
+
+        let json = replay.to_json()?;
+        assert!(json.contains("\"gameVersion\""));
+        assert!(json.contains("\"eventTime\""));
+        assert!(json.contains("\"noteId\":1999"));
+
+        let parsed: Replay = serde_json::from_str(&json)?;
+        assert_eq!(parsed.version, replay.version);
+        assert_eq!(parsed.info, replay.info);
+        assert_eq!(parsed.frames, replay.frames);
+        assert_eq!(parsed.notes, replay.notes);
+        assert_eq!(parsed.walls, replay.walls);
+        assert_eq!(parsed.heights, replay.heights);
+        assert_eq!(parsed.pauses, replay.pauses);
+
+        let pretty: Replay = serde_json::from_str(&replay.to_json_pretty()?)?;
+        assert_eq!(pretty.notes, replay.notes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_load_replay_with_lossy_strings() -> Result<()> {
+        let mut replay = generate_random_replay();
+        replay.info.player_name = "Ab/ユニ".to_owned();
+        let mut buf = get_replay_buffer(&replay)?;
+
+        // old versions of the BL mod saved the length in chars instead of bytes
+        let mut encoded = ReplayInt::to_le_bytes(9).to_vec();
+        encoded.extend_from_slice(replay.info.player_name.as_bytes());
+        let pos = buf
+            .windows(encoded.len())
+            .position(|w| w == encoded)
+            .unwrap();
+        buf[pos..pos + 4].copy_from_slice(&ReplayInt::to_le_bytes(5));
+
+        assert!(Replay::load(&mut Cursor::new(buf.clone())).is_err());
+
+        let options = LoadOptions {
+            lossy_strings: true,
+            ..Default::default()
+        };
+        let (loaded, _) = Replay::load_with_options(&mut Cursor::new(buf), &options)?;
+
+        assert_eq!(loaded.info.player_name, replay.info.player_name);
+        assert_eq!(loaded.info.hash, replay.info.hash);
+        assert_eq!(loaded.notes, replay.notes);
+        assert_eq!(loaded.pauses, replay.pauses);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_invalid_bsor_error_for_bogus_block_items_count() {
+        for count in [-1, ReplayInt::MAX] {
+            let mut buf = Vec::from([BlockType::Frames.try_into().unwrap()]);
+            buf.append(&mut ReplayInt::to_le_bytes(count).to_vec());
+
+            let result = Frames::load(&mut Cursor::new(buf));
+
+            assert!(matches!(result, Err(BsorError::InvalidBsor)));
+        }
+
+        let mut buf = Vec::from([BlockType::Notes.try_into().unwrap()]);
+        buf.append(&mut ReplayInt::to_le_bytes(1000).to_vec());
+        let options = LoadOptions {
+            max_block_items: 100,
+            ..Default::default()
+        };
+
+        let result = Notes::load_with_options(
+            &mut read_utils::PeekReader::new(Cursor::new(buf.clone())),
+            0,
+            &options,
+            &mut Vec::new(),
+        );
+        assert!(matches!(result, Err(BsorError::InvalidBsor)));
+
+        // within the cap the block is read until the stream ends
+        let result = Notes::load(&mut Cursor::new(buf));
+        assert!(matches!(result, Err(BsorError::Io(_))));
+    }
+
+    #[test]
+    fn it_can_load_replay_with_bad_notes_without_cut_info() -> Result<()> {
+        let mut replay = generate_random_replay();
+        let mut bad = generate_random_note(NoteEventType::Bad);
+        bad.cut_info = None;
+        replay.notes = Notes::new(vec![
+            bad.clone(),
+            generate_random_note(NoteEventType::Good),
+            bad,
+        ]);
+
+        let buf = get_replay_buffer(&replay)?;
+
+        let options = LoadOptions {
+            detect_bad_notes_without_cut_info: true,
+            ..Default::default()
+        };
+        let (result, _) = Replay::load_with_options(&mut Cursor::new(&buf), &options)?;
+        assert_eq!(result.notes, replay.notes);
+        assert_eq!(result.walls, replay.walls);
+        assert_eq!(result.pauses, replay.pauses);
+
+        let lossy = LoadOptions {
+            lossy_strings: true,
+            ..options
+        };
+        let (result, _) = Replay::load_with_options(&mut Cursor::new(&buf), &lossy)?;
+        assert_eq!(result.notes, replay.notes);
+
+        // without detection cut info is read from the bytes of the subsequent notes
+        assert!(!matches!(
+            Replay::load(&mut Cursor::new(&buf)),
+            Ok(result) if result.notes == replay.notes
+        ));
+        let mut written = Vec::new();
+        result.write(&mut written)?;
+        assert_eq!(written, buf);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_invalid_bsor_error_for_info_string_exceeding_max_len() -> Result<()> {
+        let mut replay = generate_random_replay();
+        replay.info.player_name = "a".repeat(100);
+        let buf = get_replay_buffer(&replay)?;
+
+        let options = LoadOptions {
+            max_string_len: 50,
+            ..Default::default()
+        };
+        let result = Replay::load_with_options(&mut Cursor::new(buf.clone()), &options);
+        assert!(matches!(result, Err(BsorError::InvalidBsor)));
+
+        assert!(Replay::load(&mut Cursor::new(buf)).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_assert_roundtrip() -> Result<()> {
+        let mut replay = generate_random_replay();
+        let mut notes = replay.notes.to_vec();
+        notes.push(generate_bomb_with_id(1999));
+        replay.notes = Notes::new(notes);
+        replay.assert_roundtrip()?;
+
+        replay.frames = Frames::new(
+            replay
+                .frames
+                .iter()
+                .cloned()
+                .map(|mut f| {
+                    f.time = ReplayFloat::NAN;
+                    f
+                })
+                .collect(),
+        );
+        replay.assert_roundtrip()?;
+
+        // cut info of a miss is not written, so it is lost after reloading
+        let mut miss = generate_random_note(NoteEventType::Miss);
+        miss.cut_info = Some(generate_random_note_cut_info());
+        replay.notes = Notes::new(Vec::from([miss]));
+
+        assert!(matches!(
+            replay.assert_roundtrip(),
+            Err(BsorError::RoundTripMismatch(BlockType::Notes))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_compares_floats_with_epsilon() {
+        assert!(1.0.approx_eq(&(1.0 + ROUNDTRIP_EPSILON / 2.0)));
+        assert!(ReplayFloat::NAN.approx_eq(&ReplayFloat::NAN));
+        assert!(ReplayFloat::INFINITY.approx_eq(&ReplayFloat::INFINITY));
+        assert!(!1.0.approx_eq(&1.001));
+        assert!(!Some(1.0).approx_eq(&None));
+        assert!(![1.0][..].approx_eq(&[1.0, 2.0]));
+    }
+
+    #[test]
+    fn it_can_write_loaded_replay_byte_identical() -> Result<()> {
+        let buf = get_replay_buffer(&generate_random_replay())?;
+
+        let replay = Replay::load(&mut Cursor::new(buf.clone()))?;
+
+        let mut written = Vec::new();
+        replay.write(&mut written)?;
+
+        assert_eq!(written, buf);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_partially_load_truncated_replay_in_lenient_mode() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let mut buf = get_replay_buffer(&replay)?;
+        let frames_pos = ReplayIndex::index(&mut Cursor::new(&buf))?.frames.pos() as usize;
+        buf.truncate(frames_pos + 5 + 92 + 10);
+
+        let options = LoadOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let (result, warnings) = Replay::load_with_options(&mut Cursor::new(buf), &options)?;
+
+        assert_eq!(result.info, replay.info);
+        assert_eq!(result.frames.len(), 1);
+        assert_eq!(result.frames[0], replay.frames[0]);
+        assert!(result.notes.is_empty());
+        assert!(result.pauses.is_empty());
+        assert_eq!(
+            warnings,
+            vec![
+                LoadWarning::TruncatedBlock {
+                    block: "Frames",
+                    declared: 2,
+                    loaded: 1
+                },
+                LoadWarning::MissingBlock { block: "Notes" },
+                LoadWarning::MissingBlock { block: "Walls" },
+                LoadWarning::MissingBlock { block: "Heights" },
+                LoadWarning::MissingBlock { block: "Pauses" },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "Frames block truncated, loaded 1 of 2 items"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_index_replay_from_non_seekable_reader() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let buf = get_replay_buffer(&replay)?;
+
+        let reader = &mut &buf[..];
+        let (result, result_buf) = ReplayIndex::index_buffered(reader)?;
+
+        assert_eq!(result_buf, buf);
+        assert_eq!(result.info, replay.info);
+        assert_eq!(
+            result.notes.load(&mut Cursor::new(&result_buf))?,
+            replay.notes
+        );
+        assert_eq!(
+            result.pauses.load(&mut Cursor::new(&result_buf))?,
+            replay.pauses
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_load_replay_with_block_offsets() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let buf = get_replay_buffer(&replay)?;
+
+        let (result, offsets) = Replay::load_with_offsets(&mut Cursor::new(&buf))?;
+        let index = ReplayIndex::index(&mut Cursor::new(&buf))?;
+
+        assert_eq!(result.notes, replay.notes);
+        assert_eq!(result.pauses, replay.pauses);
+        assert_eq!(offsets.info, 5);
+        assert_eq!(offsets.frames, index.frames.pos());
+        assert_eq!(offsets.notes, index.notes.pos());
+        assert_eq!(offsets.walls, index.walls.pos());
+        assert_eq!(offsets.heights, index.heights.pos());
+        assert_eq!(offsets.pauses, index.pauses.pos());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_validate_cut_info_invariants() {
+        let mut replay = generate_random_replay();
+
+        assert_eq!(replay.validate_cut_info_invariants(), Ok(()));
+
+        let mut notes = replay.notes.to_vec();
+        let mut miss = generate_random_note(NoteEventType::Miss);
+        miss.cut_info = Some(generate_random_note_cut_info());
+        notes.push(miss);
+        replay.notes = Notes::new(notes);
+
+        assert_eq!(replay.validate_cut_info_invariants(), Err(vec![2]));
+    }
+
+    #[test]
+    fn it_can_validate_replay() -> Result<()> {
+        let mut replay = generate_random_replay();
+
+        let mut frames = replay.frames.to_vec();
+        for (idx, frame) in frames.iter_mut().enumerate() {
+            frame.time = idx as ReplayTime;
+            frame.fps = 90;
+        }
+        replay.frames = Frames::new(frames.clone());
+
+        let mut notes = replay.notes.to_vec();
+        for (idx, note) in notes.iter_mut().enumerate() {
+            note.event_time = idx as ReplayTime;
+            note.line_idx = 1;
+            note.line_layer = 2;
+        }
+        replay.notes = Notes::new(notes.clone());
+        replay.walls = Walls::new(Vec::new());
+        replay.heights = Heights::new(Vec::new());
+
+        let mut pauses = replay.pauses.to_vec();
+        for pause in pauses.iter_mut() {
+            pause.time = 0.5;
+        }
+        replay.pauses = Pauses::new(pauses.clone());
+
+        assert_eq!(replay.validate()?, Vec::new());
+
+        frames.swap(0, 1);
+        frames[0].fps = 0;
+        replay.frames = Frames::new(frames);
+        notes[1].line_idx = 4;
+        notes[0].event_time = -1.0;
+        notes.push(generate_random_note(NoteEventType::Miss));
+        notes.last_mut().unwrap().event_time = notes.len() as ReplayTime;
+        notes.last_mut().unwrap().cut_info = Some(generate_random_note_cut_info());
+        replay.notes = Notes::new(notes.clone());
+        pauses[1].time = 5.0;
+        replay.pauses = Pauses::new(pauses);
+
+        assert_eq!(
+            replay.validate()?,
+            vec![
+                ValidationWarning::UnsortedItem {
+                    block: "Frames",
+                    idx: 1
+                },
+                ValidationWarning::InvalidFps { idx: 0, fps: 0 },
+                ValidationWarning::PauseOutOfRange { idx: 1, time: 5.0 },
+                ValidationWarning::NoteOutOfGrid {
+                    idx: 1,
+                    line_idx: 4,
+                    line_layer: 2
+                },
+                ValidationWarning::InvalidCutInfo {
+                    idx: notes.len() - 1
+                },
+                ValidationWarning::NegativeTime {
+                    block: "Notes",
+                    idx: 0
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_convert_block_type_from_and_into_id() -> Result<()> {
+        for bt in [
+            BlockType::Info,
+            BlockType::Frames,
+            BlockType::Notes,
+            BlockType::Walls,
+            BlockType::Heights,
+            BlockType::Pauses,
+        ] {
+            let id: u8 = bt.try_into()?;
+
+            assert_eq!(BlockType::try_from(id)?, bt);
+        }
+
+        assert!(matches!(
+            BlockType::try_from(6),
+            Err(BsorError::InvalidBsor)
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn it_can_index_replay() -> Result<()> {
         let replay = generate_random_replay();
@@ -309,4 +2078,695 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn it_can_load_each_block_from_indexed_replay() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let reader = &mut Cursor::new(get_replay_buffer(&replay)?);
+        let index = ReplayIndex::index(reader)?;
+
+        // blocks are loaded out of order to make sure each of them seeks to its own position
+        assert_eq!(index.pauses.load(reader)?, replay.pauses);
+        assert_eq!(index.heights.load(reader)?, replay.heights);
+        assert_eq!(index.walls.load(reader)?, replay.walls);
+        assert_eq!(index.notes.load(reader)?, replay.notes);
+        assert_eq!(index.frames.load(reader)?, replay.frames);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_reconstruct_energy_curve() {
+        let mut replay = generate_random_replay();
+        replay.notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Bad, ColorType::Red, 2.0),
+            generate_note_at(NoteEventType::Miss, ColorType::Blue, 4.0),
+        ]));
+        let mut wall = generate_random_wall();
+        wall.time = 3.0;
+        wall.energy = 0.6;
+        replay.walls = Walls::new(Vec::from([wall]));
+
+        let curve = replay.energy_curve();
+
+        let expected = [(1.0, 0.51), (2.0, 0.41), (3.0, 0.6), (4.0, 0.45)];
+        assert_eq!(curve.len(), expected.len());
+        for ((time, energy), (expected_time, expected_energy)) in curve.iter().zip(expected) {
+            assert_eq!(*time, expected_time);
+            assert!((energy - expected_energy).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn it_detects_triggered_no_fail() {
+        let mut replay = generate_random_replay();
+        replay.notes = Notes::new(
+            (0..4)
+                .map(|i| generate_note_at(NoteEventType::Miss, ColorType::Red, i as ReplayTime))
+                .chain([generate_note_at(NoteEventType::Good, ColorType::Red, 5.0)])
+                .collect(),
+        );
+        replay.walls = Walls::new(Vec::new());
+
+        replay.info.modifiers = "NF,FS".to_owned();
+        assert!(replay.no_fail_triggered());
+
+        replay.info.modifiers = "FS".to_owned();
+        assert!(!replay.no_fail_triggered());
+
+        replay.info.modifiers = "NF".to_owned();
+        replay.notes = Notes::new(Vec::from([generate_note_at(
+            NoteEventType::Miss,
+            ColorType::Red,
+            1.0,
+        )]));
+        assert!(!replay.no_fail_triggered());
+    }
+
+    #[test]
+    fn it_reports_expected_and_found_block_when_block_id_is_invalid() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let mut buf = get_replay_buffer(&replay)?;
+        let notes_pos = ReplayIndex::index(&mut Cursor::new(&buf))?.notes.pos() as usize;
+        buf[notes_pos] = BlockType::Walls.try_into()?;
+
+        let result = Replay::load(&mut Cursor::new(&buf));
+
+        assert!(matches!(
+            result,
+            Err(BsorError::UnexpectedBlock {
+                expected: 2,
+                found: 3,
+                offset,
+            }) if offset == notes_pos as u64
+        ));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "unexpected block: expected Notes block, found Walls block at offset {}",
+                notes_pos
+            )
+        );
+
+        let options = LoadOptions {
+            lossy_strings: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Replay::load_with_options(&mut Cursor::new(&buf), &options),
+            Err(BsorError::UnexpectedBlock { offset, .. }) if offset == notes_pos as u64
+        ));
+        assert!(matches!(
+            ReplayIndex::index(&mut Cursor::new(&buf)),
+            Err(BsorError::UnexpectedBlock { offset, .. }) if offset == notes_pos as u64
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_compute_active_duration() {
+        let mut replay = generate_random_replay();
+
+        let mut first_frame = generate_random_frame();
+        first_frame.time = 0.5;
+        let mut last_frame = generate_random_frame();
+        last_frame.time = 100.0;
+        replay.frames = Frames::new(Vec::from([first_frame, last_frame]));
+        replay.pauses = Pauses::new(Vec::from([
+            Pause {
+                duration: 2,
+                time: 10.0,
+            },
+            Pause {
+                duration: 5,
+                time: 30.0,
+            },
+        ]));
+
+        assert_eq!(replay.active_duration(), 93.0);
+
+        replay.frames = Frames::new(Vec::new());
+        assert_eq!(replay.active_duration(), 0.0);
+    }
+
+    #[test]
+    fn it_can_compute_time_span() {
+        let mut replay = generate_random_replay();
+
+        let mut first_frame = generate_random_frame();
+        first_frame.time = 0.5;
+        let mut last_frame = generate_random_frame();
+        last_frame.time = 100.0;
+        replay.frames = Frames::new(Vec::from([first_frame, last_frame]));
+        replay.notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Miss, ColorType::Blue, 101.5),
+        ]));
+        replay.walls = Walls::new(Vec::new());
+        replay.heights = Heights::new(Vec::new());
+
+        assert_eq!(replay.time_span(), (0.5, 101.5));
+
+        replay.frames = Frames::new(Vec::new());
+        replay.notes = Notes::new(Vec::new());
+        assert_eq!(replay.time_span(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn it_can_resume_parsing_from_saved_block_position() -> Result<()> {
+        let replay = generate_random_replay();
+        let buf = get_replay_buffer(&replay)?;
+        let mut r = Cursor::new(&buf);
+
+        let replay_index = ReplayIndex::index(&mut r)?;
+        let checkpoint = replay_index.notes.end_pos();
+        assert_eq!(checkpoint, replay_index.walls.pos());
+
+        let walls_index = BlockIndex::<Walls>::resume_from(&mut r, checkpoint)?;
+        assert_eq!(walls_index.pos(), replay_index.walls.pos());
+        assert_eq!(walls_index.bytes(), replay_index.walls.bytes());
+        assert_eq!(walls_index.load(&mut r)?, replay.walls);
+
+        let heights_index = BlockIndex::<Heights>::resume_from(&mut r, walls_index.end_pos())?;
+        assert_eq!(heights_index.load(&mut r)?, replay.heights);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_compute_accuracy() {
+        let mut replay = generate_random_replay();
+        replay.notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 2.0),
+        ]));
+        replay.info.score = 276;
+
+        assert_eq!(replay.accuracy(), Some(0.8));
+
+        replay.info.score = -1;
+        assert_eq!(replay.accuracy(), None);
+
+        replay.notes = Notes::new(Vec::new());
+        assert_eq!(replay.accuracy(), None);
+    }
+
+    #[test]
+    fn it_can_compute_scoring_events() {
+        let mut replay = generate_random_replay();
+        let mut notes = Vec::from([
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_good_note_with_ratings(1.0, 1.0, 0.3),
+            generate_random_note(NoteEventType::Miss),
+            generate_random_note(NoteEventType::Bomb),
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_good_note_with_ratings(0.5, 1.0, 0.0),
+        ]);
+        for (idx, note) in notes.iter_mut().enumerate() {
+            note.scoring_type = NoteScoringType::Normal;
+            note.event_time = idx as ReplayTime;
+        }
+        notes.swap(0, 1);
+        replay.notes = Notes::new(notes);
+
+        let events = replay.scoring_events();
+
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| (e.note_idx, e.score, e.combo, e.multiplier))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, 115, 1, 1),
+                (0, 200, 2, 2),
+                (2, 0, 0, 1),
+                (4, 115, 1, 1),
+                (5, 160, 2, 2)
+            ]
+        );
+
+        let total: u32 = events.iter().map(|e| e.score).sum();
+        assert_eq!(total, 115 + 100 * 2 + 115 + 80 * 2);
+        assert_eq!(events.last().unwrap().total_score, total);
+        assert!(Replay {
+            notes: Notes::new(Vec::new()),
+            ..replay
+        }
+        .scoring_events()
+        .is_empty());
+    }
+
+    #[test]
+    fn it_can_compute_normalized_accuracy_curve() {
+        let mut replay = generate_random_replay();
+        let mut notes = Vec::from([
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_random_note(NoteEventType::Miss),
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+        ]);
+        for (idx, note) in notes.iter_mut().enumerate() {
+            note.scoring_type = NoteScoringType::Normal;
+            note.event_time = 10.0 + idx as ReplayTime;
+        }
+        replay.notes = Notes::new(notes);
+
+        let curve = replay.normalized_accuracy_curve(9);
+
+        assert_eq!(curve.len(), 9);
+        assert!(curve.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(curve[0], (0.0, 1.0));
+        assert_eq!(curve[1], (0.125, 1.0));
+        // 2 perfect cuts (115 + 230) of 115 + 230 + 230
+        assert!((curve[4].1 - 345.0 / 575.0).abs() < 0.0001);
+        assert_eq!(curve[8].0, 1.0);
+        assert!(curve[8].1 < 1.0);
+
+        assert!(replay.normalized_accuracy_curve(0).is_empty());
+        replay.notes = Notes::new(Vec::new());
+        assert!(replay.normalized_accuracy_curve(10).is_empty());
+    }
+
+    #[test]
+    fn it_can_match_notes_with_nearest_frames() {
+        let mut replay = generate_random_replay();
+        replay.frames = Frames::new(
+            (0..4)
+                .map(|i| {
+                    let mut frame = generate_random_frame();
+                    frame.time = i as ReplayTime;
+
+                    frame
+                })
+                .collect(),
+        );
+        replay.notes = Notes::new(
+            [-0.5, 1.4, 1.5, 1.6, 5.0, 0.2]
+                .iter()
+                .map(|time| generate_note_at(NoteEventType::Good, ColorType::Red, *time))
+                .collect(),
+        );
+
+        let matched = replay
+            .notes_with_poses()
+            .map(|(note, frame)| (note.event_time, frame.map(|f| f.time)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            matched,
+            vec![
+                (-0.5, Some(0.0)),
+                (1.4, Some(1.0)),
+                (1.5, Some(1.0)),
+                (1.6, Some(2.0)),
+                (5.0, Some(3.0)),
+                (0.2, Some(0.0)),
+            ]
+        );
+        for (note, frame) in replay.notes_with_poses() {
+            assert_eq!(
+                frame,
+                replay.frames.nearest_frame(SongTime(note.event_time))
+            );
+        }
+
+        replay.frames = Frames::new(Vec::new());
+        assert!(replay.notes_with_poses().all(|(_, frame)| frame.is_none()));
+    }
+
+    #[test]
+    fn it_can_compute_frame_coverage() {
+        let mut replay = generate_random_replay();
+        replay.frames = Frames::new(
+            (0..=20)
+                .map(|i| {
+                    let mut frame = generate_random_frame();
+                    frame.time = i as ReplayTime * 0.05;
+
+                    frame
+                })
+                .collect(),
+        );
+        replay.notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 0.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 0.52),
+            generate_note_at(NoteEventType::Miss, ColorType::Red, 1.05),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 2.0),
+        ]));
+
+        assert_eq!(replay.frame_coverage(), 0.75);
+
+        replay.frames = Frames::new(Vec::new());
+        assert_eq!(replay.frame_coverage(), 0.0);
+    }
+
+    #[test]
+    fn it_can_verify_structure() -> Result<()> {
+        let replay = generate_random_replay();
+        let mut buf = get_replay_buffer(&replay)?;
+
+        assert!(Replay::verify_structure(&mut Cursor::new(&buf)).is_ok());
+
+        let mut truncated = buf.clone();
+        truncated.truncate(buf.len() - 3);
+        assert!(matches!(
+            Replay::verify_structure(&mut Cursor::new(&truncated)),
+            Err(BsorError::Io(_))
+        ));
+
+        buf.push(0);
+        assert!(matches!(
+            Replay::verify_structure(&mut Cursor::new(&buf)),
+            Err(BsorError::InvalidBsor)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_detect_rotating_mode() {
+        let mut replay = generate_random_replay();
+        replay.info.mode = "Standard".to_owned();
+
+        let frames_turning_by = |degrees: ReplayFloat| {
+            Frames::new(
+                (0..=10)
+                    .map(|i| {
+                        let half = (i as ReplayFloat * degrees / 10.0).to_radians() / 2.0;
+                        let mut frame = generate_random_frame();
+                        frame.head.rotation = vector::Vector4 {
+                            x: 0.0,
+                            y: half.sin(),
+                            z: 0.0,
+                            w: half.cos(),
+                        };
+
+                        frame
+                    })
+                    .collect(),
+            )
+        };
+
+        replay.frames = frames_turning_by(60.0);
+        assert!(!replay.is_rotating_mode());
+
+        replay.frames = frames_turning_by(-270.0);
+        assert!(replay.is_rotating_mode());
+
+        replay.frames = frames_turning_by(0.0);
+        replay.info.mode = "360Degree".to_owned();
+        assert!(replay.is_rotating_mode());
+    }
+
+    struct CountingReader<R> {
+        inner: R,
+        reads: usize,
+    }
+
+    impl<R: Read> Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.reads += 1;
+            self.inner.read(buf)
+        }
+    }
+
+    impl<R: Seek> Seek for CountingReader<R> {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn it_loads_each_block_at_most_once_with_get_or_load() -> Result<()> {
+        let replay = generate_random_replay();
+        let buf = get_replay_buffer(&replay)?;
+        let mut r = CountingReader {
+            inner: Cursor::new(buf),
+            reads: 0,
+        };
+
+        let replay_index = ReplayIndex::index(&mut r)?;
+
+        let reads = r.reads;
+        assert_eq!(
+            *replay_index.get_or_load(&mut r, BlockType::Notes)?,
+            LoadedBlock::Notes(replay.notes.clone())
+        );
+        assert!(r.reads > reads);
+
+        let reads = r.reads;
+        let notes = replay_index.get_or_load(&mut r, BlockType::Notes)?;
+        assert_eq!(*notes, LoadedBlock::Notes(replay.notes.clone()));
+        assert_eq!(r.reads, reads);
+
+        // loading another block while still holding the previous one
+        let walls = replay_index.get_or_load(&mut r, BlockType::Walls)?;
+        assert_eq!(*walls, LoadedBlock::Walls(replay.walls.clone()));
+        assert_eq!(*notes, LoadedBlock::Notes(replay.notes.clone()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_share_replay_index_between_threads() -> Result<()> {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ReplayIndex>();
+
+        let replay = generate_random_replay();
+        let buf = get_replay_buffer(&replay)?;
+
+        let replay_index = ReplayIndex::index(&mut Cursor::new(&buf))?;
+
+        std::thread::scope(|s| {
+            for _ in 0..2 {
+                s.spawn(|| {
+                    let notes = replay_index
+                        .get_or_load(&mut Cursor::new(&buf), BlockType::Notes)
+                        .unwrap();
+                    assert_eq!(*notes, LoadedBlock::Notes(replay.notes.clone()));
+                });
+            }
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_compare_runs() {
+        let mut replay = generate_random_replay();
+        replay.notes = Notes::new(
+            (0..4)
+                .map(|i| {
+                    let mut note = generate_good_note_with_ratings(1.0, 1.0, 0.0);
+                    note.spawn_time = i as ReplayTime;
+                    note.event_time = i as ReplayTime;
+
+                    note
+                })
+                .collect(),
+        );
+
+        let mut worse = generate_random_replay();
+        let mut notes = replay.notes.to_vec();
+        notes[1].cut_info.as_mut().unwrap().cut_distance_to_center = 0.1;
+        notes[2].event_type = NoteEventType::Miss;
+        notes[2].cut_info = None;
+        notes.pop();
+        worse.notes = Notes::new(notes);
+
+        let result = replay.compare_runs(&worse);
+
+        assert_eq!(
+            result,
+            vec![
+                NoteComparison {
+                    note_idx: Some(0),
+                    other_idx: Some(0),
+                    score_delta: 0,
+                    accuracy_delta: 0
+                },
+                NoteComparison {
+                    note_idx: Some(1),
+                    other_idx: Some(1),
+                    score_delta: -5,
+                    accuracy_delta: -5
+                },
+                NoteComparison {
+                    note_idx: Some(2),
+                    other_idx: Some(2),
+                    score_delta: -115,
+                    accuracy_delta: -15
+                },
+                NoteComparison {
+                    note_idx: Some(3),
+                    other_idx: None,
+                    score_delta: -115,
+                    accuracy_delta: -15
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_can_compute_energy_per_second() {
+        let mut replay = generate_random_replay();
+        replay.frames = Frames::new(Vec::new());
+        replay.walls = Walls::new(Vec::new());
+        replay.heights = Heights::new(Vec::new());
+        replay.notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Bad, ColorType::Red, 0.5),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 0.7),
+            generate_note_at(NoteEventType::Miss, ColorType::Blue, 2.2),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 4.5),
+        ]));
+
+        let result = replay.energy_per_second();
+
+        assert_eq!(
+            result.iter().map(|(second, _)| *second).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        let expected = [0.41, 0.41, 0.26, 0.26, 0.27];
+        for ((_, energy), expected) in result.iter().zip(expected) {
+            assert!((energy - expected).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn it_can_normalize_times() {
+        let mut replay = generate_random_replay();
+
+        let mut frames = Vec::new();
+        for time in [10.0, 10.5, 12.0] {
+            let mut frame = generate_random_frame();
+            frame.time = time;
+            frames.push(frame);
+        }
+        replay.frames = Frames::new(frames);
+        replay.notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 11.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 13.0),
+        ]));
+        replay.walls = Walls::new(Vec::new());
+        replay.heights = Heights::new(Vec::new());
+        replay.pauses = Pauses::new(Vec::from([Pause {
+            duration: 5,
+            time: 9.5,
+        }]));
+        replay.info.start_time = 10.0;
+        replay.info.fail_time = 0.0;
+
+        let offset = replay.normalize_times();
+
+        assert_eq!(offset, 9.5);
+        assert_eq!(replay.time_span(), (0.5, 3.5));
+        assert_eq!(
+            replay.frames.iter().map(|f| f.time).collect::<Vec<_>>(),
+            vec![0.5, 1.0, 2.5]
+        );
+        assert_eq!(replay.notes[0].event_time, 1.5);
+        assert_eq!(replay.notes[1].spawn_time, 3.5);
+        assert_eq!(replay.pauses[0].time, 0.0);
+        assert_eq!(replay.info.start_time, 0.5);
+        assert_eq!(replay.info.fail_time, 0.0);
+    }
+
+    #[test]
+    fn it_can_find_negative_time_events() {
+        let mut replay = generate_random_replay();
+        replay.frames = Frames::new(Vec::new());
+        replay.walls = Walls::new(Vec::new());
+        replay.heights = Heights::new(Vec::new());
+        replay.pauses = Pauses::new(Vec::new());
+        replay.notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Red, -0.5),
+        ]));
+
+        assert_eq!(replay.negative_time_events(), vec![("Notes", 1)]);
+
+        let mut frame = generate_random_frame();
+        frame.time = -1.0;
+        replay.frames = Frames::new(Vec::from([frame]));
+
+        assert_eq!(
+            replay.negative_time_events(),
+            vec![("Frames", 0), ("Notes", 1)]
+        );
+    }
+
+    #[test]
+    fn it_can_compute_modified_score() {
+        let mut replay = generate_random_replay();
+        replay.info.score = 1_000_001;
+
+        replay.info.modifiers = "FS".to_owned();
+        assert_eq!(replay.modified_score(), 1_080_001);
+
+        replay.info.modifiers = String::new();
+        assert_eq!(replay.modified_score(), 1_000_001);
+
+        replay.info.modifiers = "FS,ZM".to_owned();
+        assert_eq!(replay.modified_score(), 0);
+
+        replay.info.score = 1_000_000;
+        replay.info.modifiers = "SS".to_owned();
+        assert_eq!(replay.modified_score(), 700_000);
+    }
+
+    #[test]
+    fn it_returns_static_block_sizes() {
+        assert_eq!(
+            block_static_sizes(),
+            [
+                (BlockType::Info, 1),
+                (BlockType::Frames, 5),
+                (BlockType::Notes, 5),
+                (BlockType::Walls, 5),
+                (BlockType::Heights, 5),
+                (BlockType::Pauses, 5),
+            ]
+        );
+        assert_eq!(
+            item_static_sizes(),
+            [
+                (BlockType::Info, None),
+                (BlockType::Frames, Some(92)),
+                (BlockType::Notes, None),
+                (BlockType::Walls, Some(16)),
+                (BlockType::Heights, Some(8)),
+                (BlockType::Pauses, Some(12)),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_can_compute_completion_ratio() {
+        let mut replay = generate_random_replay();
+        replay.notes = Notes::new(
+            (1..=4)
+                .map(|i| generate_note_at(NoteEventType::Good, ColorType::Red, i as ReplayTime))
+                .collect(),
+        );
+        let mut frame = generate_random_frame();
+        frame.time = 1.5;
+        replay.frames = Frames::new(Vec::from([frame]));
+        replay.info.score = 1000;
+
+        replay.info.fail_time = 0.0;
+        assert_eq!(replay.completion_ratio(), 1.0);
+
+        replay.info.fail_time = 3.5;
+        assert_eq!(replay.completion_ratio(), 0.75);
+
+        replay.info.fail_time = 0.0;
+        replay.info.score = -1;
+        assert_eq!(replay.completion_ratio(), 0.25);
+    }
 }
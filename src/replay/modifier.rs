@@ -0,0 +1,164 @@
+//! enum of gameplay modifiers stored in the Info block
+use super::ReplayFloat;
+use std::fmt;
+
+/// Gameplay modifier, stored in [crate::replay::info::Info::modifiers] as a comma-separated list of codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    DisappearingArrows,
+    FasterSong,
+    SlowerSong,
+    SuperFastSong,
+    GhostNotes,
+    NoArrows,
+    NoBombs,
+    NoFail,
+    NoObstacles,
+    ProMode,
+    SmallCubes,
+    StrictAngles,
+    OldDots,
+    InstaFail,
+    BatteryEnergy,
+    ZenMode,
+}
+
+/// All modifiers along with their codes and score multiplier changes, as used by BeatLeader
+const MODIFIERS: [(Modifier, &str, ReplayFloat); 16] = [
+    (Modifier::DisappearingArrows, "DA", 0.07),
+    (Modifier::FasterSong, "FS", 0.08),
+    (Modifier::SlowerSong, "SS", -0.30),
+    (Modifier::SuperFastSong, "SF", 0.10),
+    (Modifier::GhostNotes, "GN", 0.04),
+    (Modifier::NoArrows, "NA", -0.30),
+    (Modifier::NoBombs, "NB", -0.20),
+    (Modifier::NoFail, "NF", -0.50),
+    (Modifier::NoObstacles, "NO", -0.20),
+    (Modifier::ProMode, "PM", 0.12),
+    (Modifier::SmallCubes, "SC", 0.11),
+    (Modifier::StrictAngles, "SA", 0.0),
+    (Modifier::OldDots, "OD", 0.0),
+    (Modifier::InstaFail, "IF", 0.0),
+    (Modifier::BatteryEnergy, "BE", 0.0),
+    (Modifier::ZenMode, "ZM", 0.0),
+];
+
+impl Modifier {
+    /// Returns modifier with given code (e.g. `FS`), or `None` if the code is unknown
+    pub fn from_code(code: &str) -> Option<Modifier> {
+        MODIFIERS
+            .iter()
+            .find(|(_, c, _)| *c == code)
+            .map(|(modifier, _, _)| *modifier)
+    }
+
+    /// Returns code of the modifier as stored in the replay
+    pub fn code(&self) -> &'static str {
+        self.entry().1
+    }
+
+    /// Returns change of the score multiplier when the modifier is on
+    pub fn score_multiplier_change(&self) -> ReplayFloat {
+        self.entry().2
+    }
+
+    /// Returns whether the modifier makes the score not count at all
+    pub fn is_disqualifying(&self) -> bool {
+        *self == Modifier::ZenMode
+    }
+
+    /// Parses comma-separated list of modifier codes, ignoring unknown ones
+    pub fn parse_list(modifiers: &str) -> Vec<Modifier> {
+        modifiers
+            .split(',')
+            .filter_map(|code| Modifier::from_code(code.trim()))
+            .collect()
+    }
+
+    /// Joins modifier codes into a comma-separated list, the inverse of [Modifier::parse_list()]
+    pub fn join_list(modifiers: &[Modifier]) -> String {
+        modifiers
+            .iter()
+            .map(Modifier::code)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn entry(&self) -> &'static (Modifier, &'static str, ReplayFloat) {
+        MODIFIERS
+            .iter()
+            .find(|(modifier, _, _)| modifier == self)
+            .expect("every modifier is listed")
+    }
+}
+
+impl fmt::Display for Modifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_can_convert_modifier_codes() {
+        for (modifier, code, _) in MODIFIERS {
+            assert_eq!(Modifier::from_code(code), Some(modifier));
+            assert_eq!(modifier.code(), code);
+            assert_eq!(modifier.to_string(), code);
+        }
+
+        assert_eq!(Modifier::from_code("SS"), Some(Modifier::SlowerSong));
+        assert_eq!(Modifier::from_code("SF"), Some(Modifier::SuperFastSong));
+        assert_eq!(Modifier::from_code("XX"), None);
+        assert_eq!(Modifier::from_code(""), None);
+    }
+
+    #[test]
+    fn it_can_parse_every_modifier_code() {
+        let expected = [
+            ("DA", Modifier::DisappearingArrows),
+            ("FS", Modifier::FasterSong),
+            ("SS", Modifier::SlowerSong),
+            ("SF", Modifier::SuperFastSong),
+            ("GN", Modifier::GhostNotes),
+            ("NA", Modifier::NoArrows),
+            ("NB", Modifier::NoBombs),
+            ("NF", Modifier::NoFail),
+            ("NO", Modifier::NoObstacles),
+            ("PM", Modifier::ProMode),
+            ("SC", Modifier::SmallCubes),
+            ("SA", Modifier::StrictAngles),
+            ("OD", Modifier::OldDots),
+            ("IF", Modifier::InstaFail),
+            ("BE", Modifier::BatteryEnergy),
+            ("ZM", Modifier::ZenMode),
+        ];
+
+        for (code, modifier) in expected {
+            assert_eq!(Modifier::parse_list(code), vec![modifier]);
+        }
+        assert_eq!(Modifier::SlowerSong.score_multiplier_change(), -0.30);
+        assert_eq!(Modifier::SuperFastSong.score_multiplier_change(), 0.10);
+    }
+
+    #[test]
+    fn it_can_parse_and_join_modifier_list() {
+        let modifiers = Modifier::parse_list("DA, FS,XX,,NF");
+
+        assert_eq!(
+            modifiers,
+            vec![
+                Modifier::DisappearingArrows,
+                Modifier::FasterSong,
+                Modifier::NoFail
+            ]
+        );
+        assert_eq!(Modifier::join_list(&modifiers), "DA,FS,NF");
+
+        assert!(Modifier::parse_list("").is_empty());
+        assert_eq!(Modifier::join_list(&[]), "");
+    }
+}
@@ -1,35 +1,67 @@
 //! structs storing the Notes block data
+use crate::replay::read_utils::PeekReader;
+use crate::replay::score::{self, ScoreMultiplier};
 use crate::replay::{
-    assert_start_of_block, read_utils, vector::Vector3, BlockIndex, BlockType, BsorError,
-    GetStaticBlockSize, LineIdx, LineLayer, LoadBlock, LoadRealBlockSize, ReplayFloat, ReplayInt,
-    ReplayTime, Result,
+    expect_block, load_block_items, read_utils, vector::Vector3, write_block_items, write_utils,
+    ApproxEq, BlockIndex, BlockType, BsorError, GetStaticBlockSize, LineIdx, LineLayer, LoadBlock,
+    LoadOptions, LoadRealBlockSize, LoadWarning, ReplayFloat, ReplayInt, ReplayTime, Result,
 };
-use std::io::{Read, Seek, SeekFrom};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::marker::PhantomData;
 use std::mem::size_of;
 use std::ops::Deref;
 
 /// Struct implements [std::ops::Deref] trait so it could be treated as Vec<[Note]>
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Notes(Vec<Note>);
 
 impl Notes {
-    #[cfg(test)]
-    pub(crate) fn new(vec: Vec<Note>) -> Notes {
+    /// Creates block from given items
+    pub fn new(vec: Vec<Note>) -> Notes {
         Notes(vec)
     }
 
-    pub(crate) fn load<R: Read>(r: &mut R) -> Result<Notes> {
-        assert_start_of_block(r, BlockType::Notes)?;
+    pub(crate) fn load<RS: Read + Seek>(r: &mut RS) -> Result<Notes> {
+        let offset = r.stream_position()?;
 
-        let count = read_utils::read_int(r)? as usize;
-        let mut vec = Vec::<Note>::with_capacity(count);
+        Self::load_with_options(r, offset, &LoadOptions::default(), &mut Vec::new())
+    }
 
-        for _ in 0..count {
-            vec.push(Note::load(r)?);
-        }
+    pub(crate) fn load_with_options<R: Read>(
+        r: &mut R,
+        offset: u64,
+        options: &LoadOptions,
+        warnings: &mut Vec<LoadWarning>,
+    ) -> Result<Notes> {
+        Ok(Notes(load_block_items(
+            r,
+            BlockType::Notes,
+            offset,
+            options,
+            warnings,
+            Note::load,
+        )?))
+    }
 
-        Ok(Notes(vec))
+    /// Loads Notes block telling Bad notes without cut info apart, see [LoadOptions::detect_bad_notes_without_cut_info]
+    pub(crate) fn load_detecting_cut_info<R: Read>(
+        r: &mut PeekReader<R>,
+        offset: u64,
+        options: &LoadOptions,
+        warnings: &mut Vec<LoadWarning>,
+    ) -> Result<Notes> {
+        Ok(Notes(load_block_items(
+            r,
+            BlockType::Notes,
+            offset,
+            options,
+            warnings,
+            Note::load_detecting_cut_info,
+        )?))
     }
 
     pub(crate) fn load_block<RS: Read + Seek>(
@@ -40,6 +72,600 @@ impl Notes {
 
         Self::load(r)
     }
+
+    /// Writes the block id, items count and all items
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_block_items(w, BlockType::Notes, &self.0, Note::write)
+    }
+
+    /// Splits notes (bombs excluded) into contiguous runs labeled with the pattern they form
+    ///
+    /// Notes are grouped by their `spawn_time` (the time the note is placed on the map), and then:
+    /// * two notes of different colors sharing the same time form a jump, consecutive jumps are merged into a single [PatternKind::Jump] segment
+    /// * at least [MIN_STREAM_LENGTH] consecutive single notes alternating colors, spaced evenly (each interval within 10% of the first one) form a [PatternKind::Stream] segment
+    /// * anything else is merged into [PatternKind::Other] segments
+    pub fn pattern_segments(&self) -> Vec<PatternSegment> {
+        let mut indices = self
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.event_type != NoteEventType::Bomb)
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        indices.sort_by(|a, b| self[*a].spawn_time.total_cmp(&self[*b].spawn_time));
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for idx in indices {
+            match groups.last_mut() {
+                Some(group)
+                    if (self[group[0]].spawn_time - self[idx].spawn_time).abs()
+                        <= PATTERN_TIME_EPSILON =>
+                {
+                    group.push(idx)
+                }
+                _ => groups.push(vec![idx]),
+            }
+        }
+
+        let mut segments: Vec<PatternSegment> = Vec::new();
+        let mut i = 0;
+        while i < groups.len() {
+            let (kind, len) = if self.is_jump(&groups[i]) {
+                (PatternKind::Jump, 1)
+            } else {
+                match self.stream_length(&groups[i..]) {
+                    len if len >= MIN_STREAM_LENGTH => (PatternKind::Stream, len),
+                    _ => (PatternKind::Other, 1),
+                }
+            };
+
+            let note_indices = groups[i..i + len].concat();
+            match segments.last_mut() {
+                Some(segment) if segment.kind == kind => {
+                    segment.note_indices.extend(note_indices);
+                    segment.end_time = self[groups[i + len - 1][0]].spawn_time;
+                }
+                _ => segments.push(PatternSegment {
+                    kind,
+                    start_time: self[groups[i][0]].spawn_time,
+                    end_time: self[groups[i + len - 1][0]].spawn_time,
+                    note_indices,
+                }),
+            }
+
+            i += len;
+        }
+
+        segments
+    }
+
+    /// Groups notes into buckets of fixed `bucket` length by their event time, returning start time of each bucket along with its notes
+    ///
+    /// Buckets are sorted by time and empty buckets are skipped, returns no buckets at all if `bucket` is not positive
+    pub fn time_buckets(&self, bucket: ReplayTime) -> Vec<(ReplayTime, Vec<&Note>)> {
+        if bucket <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut buckets = BTreeMap::<i64, Vec<&Note>>::new();
+        for note in self.iter() {
+            buckets
+                .entry((note.event_time / bucket).floor() as i64)
+                .or_default()
+                .push(note);
+        }
+
+        buckets
+            .into_iter()
+            .map(|(idx, notes)| (idx as ReplayTime * bucket, notes))
+            .collect()
+    }
+
+    /// Returns all notes whose id as stored in the replay file (see [Note::id()]) equals `id`
+    pub fn find_by_id(&self, id: ReplayInt) -> Vec<&Note> {
+        self.iter().filter(|n| n.id() == id).collect()
+    }
+
+    /// Returns the note with event time closest to `t`, but only if it differs by at most `tolerance`
+    ///
+    /// Uses binary search, so notes have to be sorted by event time (as they are in the replay file)
+    pub fn note_near_time(&self, t: ReplayTime, tolerance: ReplayTime) -> Option<&Note> {
+        self.index_near_time(t, tolerance).map(|idx| &self[idx])
+    }
+
+    /// Returns the note with event time closest to `time`, but only if it differs by at most [FIND_AT_TIME_TOLERANCE]
+    ///
+    /// Uses binary search, so notes have to be sorted by event time (as they are in the replay file), otherwise the result is unspecified
+    pub fn find_at_time(&self, time: ReplayTime) -> Option<&Note> {
+        self.note_near_time(time, FIND_AT_TIME_TOLERANCE)
+    }
+
+    /// Returns index of the note found by [Notes::find_at_time()]
+    pub fn find_index_at_time(&self, time: ReplayTime) -> Option<usize> {
+        self.index_near_time(time, FIND_AT_TIME_TOLERANCE)
+    }
+
+    /// Returns the most recent good note with event time before `t`
+    ///
+    /// Uses binary search, so notes have to be sorted by event time (as they are in the replay file)
+    pub fn last_good_before(&self, t: ReplayTime) -> Option<&Note> {
+        let idx = self.partition_point(|n| n.event_time < t);
+
+        self[..idx]
+            .iter()
+            .rev()
+            .find(|n| n.event_type == NoteEventType::Good)
+    }
+
+    fn index_near_time(&self, t: ReplayTime, tolerance: ReplayTime) -> Option<usize> {
+        let idx = self.partition_point(|n| n.event_time <= t);
+
+        [idx.checked_sub(1), Some(idx)]
+            .into_iter()
+            .flatten()
+            .filter_map(|idx| self.get(idx).map(|n| (idx, (n.event_time - t).abs())))
+            .filter(|(_, diff)| *diff <= tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(idx, _)| idx)
+    }
+
+    /// Returns number of good cuts for each expected cut direction
+    pub fn cut_direction_histogram(&self) -> HashMap<CutDirection, usize> {
+        let mut histogram = HashMap::new();
+
+        for note in self.iter().filter(|n| n.event_type == NoteEventType::Good) {
+            *histogram.entry(note.cut_direction).or_insert(0) += 1;
+        }
+
+        histogram
+    }
+
+    /// Returns iterator over notes (bombs excluded) of given color
+    pub fn by_color(&self, color: ColorType) -> impl Iterator<Item = &Note> {
+        self.iter()
+            .filter(move |n| n.color_type == color && n.event_type != NoteEventType::Bomb)
+    }
+
+    /// Returns iterator over adjacent pairs of notes of given color, in the order they appear in the replay
+    pub fn same_color_pairs(&self, color: ColorType) -> impl Iterator<Item = (&Note, &Note)> {
+        self.by_color(color).zip(self.by_color(color).skip(1))
+    }
+
+    /// Returns iterator over cut info of good cuts
+    pub fn good_cuts(&self) -> impl Iterator<Item = &NoteCutInfo> {
+        self.iter()
+            .filter(|n| n.event_type == NoteEventType::Good)
+            .filter_map(|n| n.cut_info.as_ref())
+    }
+
+    /// Returns min, max and mean saber speed of good cuts, or zeros if there are none
+    pub fn saber_speed_stats(&self) -> (f32, f32, f32) {
+        let mut count = 0;
+        let mut min = f32::MAX;
+        let mut max = f32::MIN;
+        let mut sum = 0.0;
+
+        for cut_info in self.good_cuts() {
+            count += 1;
+            min = min.min(cut_info.saber_speed);
+            max = max.max(cut_info.saber_speed);
+            sum += cut_info.saber_speed;
+        }
+
+        if count == 0 {
+            return (0.0, 0.0, 0.0);
+        }
+
+        (min, max, sum / count as f32)
+    }
+
+    /// Returns standard deviation of saber speed over good cuts of given color, lower value means more consistent swinging
+    ///
+    /// Returns 0 if there are no good cuts of that color
+    pub fn swing_speed_stddev(&self, color: ColorType) -> f32 {
+        let speeds = self
+            .by_color(color)
+            .filter(|n| n.event_type == NoteEventType::Good)
+            .filter_map(|n| n.cut_info.as_ref().map(|c| c.saber_speed))
+            .collect::<Vec<_>>();
+
+        if speeds.is_empty() {
+            return 0.0;
+        }
+
+        let count = speeds.len() as f32;
+        let mean = speeds.iter().sum::<f32>() / count;
+        let variance = speeds.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / count;
+
+        variance.sqrt()
+    }
+
+    /// Returns max score possible to get on the notes, i.e. when all scoring notes are cut perfectly and the multiplier is never decreased
+    pub fn max_possible_score(&self) -> u32 {
+        let mut multiplier = ScoreMultiplier::new();
+
+        self.iter()
+            .filter(|n| n.is_scoring())
+            .map(|n| {
+                multiplier.increase();
+
+                score::max_score(n.scoring_type) * multiplier.value() as u32
+            })
+            .sum()
+    }
+
+    /// Returns accuracy of the cuts along with the total achieved and max possible score it is computed from, ignoring the combo multiplier
+    ///
+    /// Each good cut scores its pre-swing, post-swing and accuracy points, bad cuts and misses score 0 but still count toward the max score, bombs and notes not counting toward the score are excluded. Accuracy is 0 if there are no scoring notes
+    pub fn accuracy(&self) -> (f32, u32, u32) {
+        let (achieved, max) =
+            self.iter()
+                .filter(|n| n.is_scoring())
+                .fold((0, 0), |(achieved, max), n| {
+                    (
+                        achieved + n.cut_score().unwrap_or(0),
+                        max + score::max_score(n.scoring_type),
+                    )
+                });
+
+        if max == 0 {
+            return (0.0, achieved, max);
+        }
+
+        (achieved as f32 / max as f32, achieved, max)
+    }
+
+    /// Returns mean cut point of good cuts, an offset from the note center reveals systematic aim bias, or `None` if there are no good cuts
+    pub fn cut_point_centroid(&self) -> Option<Vector3> {
+        let (count, sum) = self
+            .good_cuts()
+            .fold((0, (0.0, 0.0, 0.0)), |(count, sum), c| {
+                (
+                    count + 1,
+                    (
+                        sum.0 + c.cut_point.x,
+                        sum.1 + c.cut_point.y,
+                        sum.2 + c.cut_point.z,
+                    ),
+                )
+            });
+
+        if count == 0 {
+            return None;
+        }
+
+        Some(Vector3 {
+            x: sum.0 / count as ReplayFloat,
+            y: sum.1 / count as ReplayFloat,
+            z: sum.2 / count as ReplayFloat,
+        })
+    }
+
+    /// Returns standard deviation magnitude of good cut points around [Notes::cut_point_centroid()], or `None` if there are no good cuts
+    pub fn cut_point_spread(&self) -> Option<ReplayFloat> {
+        let centroid = self.cut_point_centroid()?;
+
+        let (count, sum) = self.good_cuts().fold((0, 0.0), |(count, sum), c| {
+            let dx = c.cut_point.x - centroid.x;
+            let dy = c.cut_point.y - centroid.y;
+            let dz = c.cut_point.z - centroid.z;
+
+            (count + 1, sum + dx * dx + dy * dy + dz * dz)
+        });
+
+        Some((sum / count as ReplayFloat).sqrt())
+    }
+
+    /// Returns indices of notes having implausible cut info, see [NoteCutInfo::is_plausible()]
+    pub fn implausible_cuts(&self) -> Vec<usize> {
+        self.iter()
+            .enumerate()
+            .filter(|(_, n)| n.cut_info.as_ref().is_some_and(|c| !c.is_plausible()))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Returns fraction of scoring notes cut perfectly, i.e. getting the max score possible for their scoring type (115 for normal notes)
+    ///
+    /// Bombs and notes not counting toward the score are excluded, returns 0 if there are no scoring notes
+    pub fn perfect_cut_ratio(&self) -> f32 {
+        let mut total = 0;
+        let mut perfect = 0;
+
+        for note in self.iter().filter(|n| n.is_scoring()) {
+            total += 1;
+
+            if note.cut_score() == Some(score::max_score(note.scoring_type)) {
+                perfect += 1;
+            }
+        }
+
+        if total == 0 {
+            return 0.0;
+        }
+
+        perfect as f32 / total as f32
+    }
+
+    /// Returns mean accuracy (cut score divided by the max score of its scoring type) of good cuts made before the first combo break, i.e. bad cut, miss or bomb hit
+    ///
+    /// Returns 0 if there are no such cuts
+    pub fn pre_break_accuracy(&self) -> f32 {
+        let mut total = 0.0;
+        let mut count = 0;
+
+        for note in self.iter() {
+            if note.resets_combo() {
+                break;
+            }
+
+            let max_score = score::max_score(note.scoring_type);
+            if let (Some(cut_score), true) = (note.cut_score(), max_score > 0) {
+                total += cut_score as f32 / max_score as f32;
+                count += 1;
+            }
+        }
+
+        if count == 0 {
+            return 0.0;
+        }
+
+        total / count as f32
+    }
+
+    /// Returns max combo achieved, combo is incremented by good cuts and reset by bad cuts, misses and bomb hits
+    ///
+    /// Notes are processed in order, i.e. sorted by event time as they are in the replay file
+    pub fn max_combo(&self) -> u32 {
+        let mut combo = 0;
+        let mut max_combo = 0;
+
+        for note in self.iter() {
+            if note.event_type == NoteEventType::Good {
+                combo += 1;
+                max_combo = max_combo.max(combo);
+            } else if note.resets_combo() {
+                combo = 0;
+            }
+        }
+
+        max_combo
+    }
+
+    /// Returns notes that broke the combo (bad cuts, misses and bomb hits), see [Notes::max_combo()]
+    pub fn combo_breaks(&self) -> Vec<&Note> {
+        self.iter().filter(|n| n.resets_combo()).collect()
+    }
+
+    /// Counts notes of each event type in a single pass
+    pub fn event_counts(&self) -> NoteCounts {
+        self.iter().fold(NoteCounts::default(), |mut counts, note| {
+            match note.event_type {
+                NoteEventType::Good => counts.good += 1,
+                NoteEventType::Bad => counts.bad += 1,
+                NoteEventType::Miss => counts.miss += 1,
+                NoteEventType::Bomb => counts.bomb += 1,
+                NoteEventType::Unknown(_) => counts.unknown += 1,
+            }
+
+            counts
+        })
+    }
+
+    /// Returns max combo achieved considering only red notes and only blue notes respectively
+    ///
+    /// Each color's combo is incremented by its good cuts and reset by its notes resetting the combo (see [Note::resets_combo()]), bomb hits have no color and reset both
+    pub fn combo_by_color(&self) -> (u32, u32) {
+        let max_combo = |color: ColorType| {
+            let mut combo = 0;
+            let mut max_combo = 0;
+
+            let notes = self
+                .iter()
+                .filter(|n| n.color_type == color || n.event_type == NoteEventType::Bomb);
+            for note in notes {
+                if note.event_type == NoteEventType::Good {
+                    combo += 1;
+                    max_combo = max_combo.max(combo);
+                } else if note.resets_combo() {
+                    combo = 0;
+                }
+            }
+
+            max_combo
+        };
+
+        (max_combo(ColorType::Red), max_combo(ColorType::Blue))
+    }
+
+    /// Returns total time (based on notes `event_time`) during which the combo multiplier was at its max value (8x)
+    pub fn time_at_full_multiplier(&self) -> ReplayTime {
+        let mut multiplier = ScoreMultiplier::new();
+        let mut full_since = None;
+        let mut total = 0.0;
+
+        for note in self.iter() {
+            if note.event_type == NoteEventType::Good {
+                multiplier.increase();
+            } else if note.resets_combo() {
+                multiplier.decrease();
+            } else {
+                continue;
+            }
+
+            match (multiplier.value() == ScoreMultiplier::MAX, full_since) {
+                (true, None) => full_since = Some(note.event_time),
+                (false, Some(since)) => {
+                    total += note.event_time - since;
+                    full_since = None;
+                }
+                _ => {}
+            }
+        }
+
+        if let (Some(since), Some(last)) = (full_since, self.last()) {
+            total += last.event_time - since;
+        }
+
+        total
+    }
+
+    /// Pairs notes with the expected map notes given as `(note id, time)`, see [Note::id()]
+    ///
+    /// Each note is matched to the nearest not yet matched expected note with the same id whose time differs from the note `spawn_time` by at most [MAP_MATCH_TOLERANCE]. Result contains an entry for each note (in order) followed by entries for expected notes left unmatched
+    pub fn match_to_map(&self, expected: &[(ReplayInt, ReplayTime)]) -> Vec<MatchResult> {
+        let mut matched = vec![false; expected.len()];
+
+        let mut result = self
+            .iter()
+            .enumerate()
+            .map(|(note_idx, note)| {
+                let id = note.id();
+
+                let nearest = expected
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, (expected_id, _))| !matched[*idx] && *expected_id == id)
+                    .map(|(idx, (_, time))| (idx, (time - note.spawn_time).abs()))
+                    .filter(|(_, diff)| *diff <= MAP_MATCH_TOLERANCE)
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+
+                match nearest {
+                    Some((expected_idx, _)) => {
+                        matched[expected_idx] = true;
+                        MatchResult::Matched {
+                            note_idx,
+                            expected_idx,
+                        }
+                    }
+                    None => MatchResult::UnmatchedNote { note_idx },
+                }
+            })
+            .collect::<Vec<_>>();
+
+        result.extend(
+            matched
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| !**m)
+                .map(|(expected_idx, _)| MatchResult::UnmatchedExpected { expected_idx }),
+        );
+
+        result
+    }
+
+    fn is_jump(&self, group: &[usize]) -> bool {
+        group.len() == 2 && self[group[0]].color_type != self[group[1]].color_type
+    }
+
+    /// Returns the number of leading single note groups forming an evenly spaced, color alternating run
+    fn stream_length(&self, groups: &[Vec<usize>]) -> usize {
+        if groups.is_empty() || groups[0].len() != 1 {
+            return 0;
+        }
+
+        let mut len = 1;
+        let mut interval = None;
+        while len < groups.len() && groups[len].len() == 1 {
+            let prev = &self[groups[len - 1][0]];
+            let current = &self[groups[len][0]];
+
+            if prev.color_type == current.color_type {
+                break;
+            }
+
+            let current_interval = current.spawn_time - prev.spawn_time;
+            let base_interval = *interval.get_or_insert(current_interval);
+            if (current_interval - base_interval).abs() > base_interval * 0.1 {
+                break;
+            }
+
+            len += 1;
+        }
+
+        len
+    }
+}
+
+/// Max difference in time of notes considered as placed at the same time
+const PATTERN_TIME_EPSILON: ReplayTime = 0.001;
+/// Min number of notes forming a stream
+pub const MIN_STREAM_LENGTH: usize = 4;
+
+/// Max difference between note event time and the time looked up, see [Notes::find_at_time()]
+pub const FIND_AT_TIME_TOLERANCE: ReplayTime = 0.1;
+
+/// Max difference between note and expected map note time, see [Notes::match_to_map()]
+pub const MAP_MATCH_TOLERANCE: ReplayTime = 0.01;
+
+/// Comparison of the same note in two runs of a map, see [crate::replay::Replay::compare_runs()]
+///
+/// Deltas are computed as the other run minus this run, a note that was not cut or is missing in a run scores 0
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteComparison {
+    /// Index of the note in this run, `None` if the note is present only in the other run
+    pub note_idx: Option<usize>,
+    /// Index of the note in the other run, `None` if the note is present only in this run
+    pub other_idx: Option<usize>,
+    /// Difference of the total cut score
+    pub score_delta: i32,
+    /// Difference of the accuracy part of the cut score
+    pub accuracy_delta: i32,
+}
+
+/// Max plausible value of cut ratings, see [NoteCutInfo::is_plausible()]
+pub const MAX_PLAUSIBLE_CUT_RATING: ReplayFloat = 5.0;
+
+/// Result of matching notes to the expected map notes, see [Notes::match_to_map()]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchResult {
+    /// Note at `note_idx` matches expected note at `expected_idx`
+    Matched {
+        note_idx: usize,
+        expected_idx: usize,
+    },
+    /// Note at `note_idx` has no matching expected note
+    UnmatchedNote { note_idx: usize },
+    /// Expected note at `expected_idx` has no matching note
+    UnmatchedExpected { expected_idx: usize },
+}
+
+/// Kind of pattern formed by a run of notes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PatternKind {
+    Stream,
+    Jump,
+    Other,
+}
+
+/// Contiguous run of notes forming a single pattern, see [Notes::pattern_segments()]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternSegment {
+    pub kind: PatternKind,
+    /// indices of the segment notes in [Notes]
+    pub note_indices: Vec<usize>,
+    /// spawn time of the first note of the segment
+    pub start_time: ReplayTime,
+    /// spawn time of the last note of the segment
+    pub end_time: ReplayTime,
+}
+
+/// Number of notes of each event type, see [Notes::event_counts()]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoteCounts {
+    pub good: usize,
+    pub bad: usize,
+    pub miss: usize,
+    pub bomb: usize,
+    pub unknown: usize,
+}
+
+/// Score of a good cut broken into its components, see [Note::score()]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoteScore {
+    pub pre_swing: u32,
+    pub post_swing: u32,
+    pub accuracy: u32,
+    pub total: u32,
 }
 
 impl Deref for Notes {
@@ -59,17 +685,19 @@ impl GetStaticBlockSize for Notes {
 impl LoadBlock for BlockIndex<Notes> {
     type Item = Notes;
 
-    /// Loads Frames block from ReplayIndex
+    /// Loads Notes block from ReplayIndex
     fn load<RS: Read + Seek>(&self, r: &mut RS) -> Result<Self::Item> {
         Self::Item::load_block(r, self)
     }
 }
 
+impl_block!(Notes, Note, [event_time, spawn_time]);
+
 impl LoadRealBlockSize for Notes {
     type Item = Notes;
 
     fn load_real_block_size<RS: Read + Seek>(r: &mut RS, pos: u64) -> Result<BlockIndex<Notes>> {
-        assert_start_of_block(r, BlockType::Notes)?;
+        expect_block(r, BlockType::Notes, pos)?;
 
         let count = read_utils::read_int(r)?;
 
@@ -92,14 +720,23 @@ impl LoadRealBlockSize for Notes {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Note {
+    /// raw note id as stored in the replay file, the fields below are decoded from it; written back only if they can not encode the id, see [Note::id()]
+    pub note_id: ReplayInt,
     pub scoring_type: NoteScoringType,
     pub line_idx: LineIdx,
     pub line_layer: LineLayer,
     pub color_type: ColorType,
     pub cut_direction: CutDirection,
+    /// song time of the event (cut, miss or bomb hit), in seconds
     pub event_time: ReplayTime,
+    /// song time the note spawned at, in seconds
     pub spawn_time: ReplayTime,
     pub event_type: NoteEventType,
     pub cut_info: Option<NoteCutInfo>,
@@ -107,9 +744,40 @@ pub struct Note {
 
 impl Note {
     pub(crate) fn load<R: Read>(r: &mut R) -> Result<Note> {
-        let mut note_id = read_utils::read_int(r)?;
+        let mut note = Self::load_without_cut_info(r)?;
+
+        if let NoteEventType::Good | NoteEventType::Bad = note.event_type {
+            note.cut_info = Some(NoteCutInfo::load(r)?);
+        }
+
+        Ok(note)
+    }
+
+    /// Loads note, looking ahead at the following bytes to tell whether a Bad note carries cut info
+    pub(crate) fn load_detecting_cut_info<R: Read>(r: &mut PeekReader<R>) -> Result<Note> {
+        let mut note = Self::load_without_cut_info(r)?;
+
+        let has_cut_info = match note.event_type {
+            NoteEventType::Good => true,
+            NoteEventType::Bad => {
+                NoteCutInfo::is_serialized_in(r.peek(NoteCutInfo::get_static_size())?)
+            }
+            _ => false,
+        };
+        if has_cut_info {
+            note.cut_info = Some(NoteCutInfo::load(r)?);
+        }
+
+        Ok(note)
+    }
+
+    fn load_without_cut_info<R: Read>(r: &mut R) -> Result<Note> {
+        let raw_id = read_utils::read_int(r)?;
+        let mut note_id = raw_id;
 
-        let scoring_type = NoteScoringType::try_from((note_id / 10000) as u8)?;
+        // scoring types not fitting in u8 would wrap around to a known one
+        let scoring_type =
+            NoteScoringType::try_from(u8::try_from(note_id / 10000).unwrap_or(u8::MAX))?;
         note_id %= 10000;
 
         let line_idx = (note_id / 1000) as LineIdx;
@@ -127,12 +795,8 @@ impl Note {
         let spawn_time = read_utils::read_float(r)?;
         let event_type = NoteEventType::try_from(read_utils::read_int(r)?)?;
 
-        let cut_info = match &event_type {
-            _x @ NoteEventType::Good | _x @ NoteEventType::Bad => Some(NoteCutInfo::load(r)?),
-            _ => None,
-        };
-
         Ok(Note {
+            note_id: raw_id,
             scoring_type,
             line_idx,
             line_layer,
@@ -141,10 +805,95 @@ impl Note {
             event_time,
             spawn_time,
             event_type,
-            cut_info,
+            cut_info: None,
         })
     }
 
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_utils::write_int(w, self.id())?;
+        write_utils::write_float(w, self.event_time)?;
+        write_utils::write_float(w, self.spawn_time)?;
+        write_utils::write_int(w, self.event_type.into())?;
+
+        match (&self.event_type, &self.cut_info) {
+            (NoteEventType::Good | NoteEventType::Bad, Some(cut_info)) => cut_info.write(w),
+            (NoteEventType::Good, None) => Err(BsorError::InvalidBsor),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns whether cut info is present exactly for Good and Bad events
+    pub(crate) fn has_valid_cut_info(&self) -> bool {
+        match self.event_type {
+            NoteEventType::Good | NoteEventType::Bad => self.cut_info.is_some(),
+            _ => self.cut_info.is_none(),
+        }
+    }
+
+    /// Returns score breakdown of a good cut, or `None` for any other event (bad cuts score no points)
+    ///
+    /// Pre-swing (0-70) and post-swing (0-30) scores come from the before and after cut ratings, accuracy (0-15) from the cut distance to the center of the note. Arcs and chains are scored the same way as in the game, e.g. chain elements always get 20 accuracy points
+    pub fn score(&self) -> Option<NoteScore> {
+        self.cut_score_components()
+            .map(|(pre_swing, post_swing, accuracy)| NoteScore {
+                pre_swing,
+                post_swing,
+                accuracy,
+                total: pre_swing + post_swing + accuracy,
+            })
+    }
+
+    /// Returns whether the note resets the combo, i.e. it is a bad cut, a miss (see [NoteEventType::breaks_combo()]) or a bomb hit
+    ///
+    /// Used by all combo computations, so they can not disagree
+    pub fn resets_combo(&self) -> bool {
+        self.event_type.breaks_combo() || self.event_type == NoteEventType::Bomb
+    }
+
+    /// Returns the pre-swing, post-swing and accuracy score of a good cut, or `None` for any other event
+    pub(crate) fn cut_score_components(&self) -> Option<(u32, u32, u32)> {
+        match (&self.event_type, &self.cut_info) {
+            (NoteEventType::Good, Some(cut_info)) => {
+                Some(score::cut_score(self.scoring_type, cut_info))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the score of a good cut, or `None` for any other event
+    pub(crate) fn cut_score(&self) -> Option<u32> {
+        self.cut_score_components()
+            .map(|(pre_swing, post_swing, accuracy)| pre_swing + post_swing + accuracy)
+    }
+
+    /// Returns whether the note counts toward the score, i.e. it is not a bomb and its scoring type has a non-zero max score
+    pub(crate) fn is_scoring(&self) -> bool {
+        matches!(
+            self.event_type,
+            NoteEventType::Good | NoteEventType::Bad | NoteEventType::Miss
+        ) && score::max_score(self.scoring_type) > 0
+    }
+
+    /// Returns note id encoded from scoring type, line index and layer, color and cut direction, as written to the replay file
+    ///
+    /// Falls back to the raw `note_id` if the fields can not encode it, i.e. any of them is unknown (as for bombs, which have no color) or line index or layer exceed one digit
+    pub fn id(&self) -> ReplayInt {
+        let encodable = self.scoring_type != NoteScoringType::Unknown
+            && self.color_type != ColorType::Unknown
+            && self.cut_direction != CutDirection::Unknown
+            && self.line_idx < 10
+            && self.line_layer < 10;
+        if !encodable {
+            return self.note_id;
+        }
+
+        self.scoring_type as ReplayInt * 10000
+            + self.line_idx as ReplayInt * 1000
+            + self.line_layer as ReplayInt * 100
+            + self.color_type as ReplayInt * 10
+            + self.cut_direction as ReplayInt
+    }
+
     pub(self) fn get_total_block_size<RS: Read + Seek>(r: &mut RS) -> Result<u64> {
         // skip to event type field
         r.seek(SeekFrom::Current(
@@ -155,9 +904,7 @@ impl Note {
 
         let bytes = Note::get_static_size() as u64
             + match &event_type {
-                _x @ NoteEventType::Good | _x @ NoteEventType::Bad => {
-                    NoteCutInfo::get_static_size() as u64
-                }
+                NoteEventType::Good | NoteEventType::Bad => NoteCutInfo::get_static_size() as u64,
                 _ => 0,
             };
 
@@ -171,7 +918,22 @@ impl GetStaticBlockSize for Note {
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl ApproxEq for Note {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+            && self.event_time.approx_eq(&other.event_time)
+            && self.spawn_time.approx_eq(&other.spawn_time)
+            && self.event_type == other.event_type
+            && self.cut_info.approx_eq(&other.cut_info)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct NoteCutInfo {
     pub speed_ok: bool,
     pub direction_ok: bool,
@@ -191,6 +953,49 @@ pub struct NoteCutInfo {
 }
 
 impl NoteCutInfo {
+    /// Returns whether the cut info values fall in plausible ranges, implausible values indicate corrupted data
+    ///
+    /// All floats have to be finite, saber speed non-negative, cut distance to center within 0..=1 and both cut ratings within 0..=[MAX_PLAUSIBLE_CUT_RATING] (ratings above 1 are valid, they come from overswinging and are clamped when scoring)
+    pub fn is_plausible(&self) -> bool {
+        let floats = [
+            self.saber_speed,
+            self.time_deviation,
+            self.cut_dir_deviation,
+            self.cut_distance_to_center,
+            self.cut_angle,
+            self.before_cut_rating,
+            self.after_cut_rating,
+        ];
+        let vectors = [&self.saber_dir, &self.cut_point, &self.cut_normal];
+
+        floats.iter().all(|f| f.is_finite())
+            && vectors
+                .iter()
+                .all(|v| v.x.is_finite() && v.y.is_finite() && v.z.is_finite())
+            && self.saber_speed >= 0.0
+            && (0.0..=1.0).contains(&self.cut_distance_to_center)
+            && (0.0..=MAX_PLAUSIBLE_CUT_RATING).contains(&self.before_cut_rating)
+            && (0.0..=MAX_PLAUSIBLE_CUT_RATING).contains(&self.after_cut_rating)
+    }
+
+    /// Returns whether the bytes start with serialized cut info, i.e. they are long enough to hold it and its bools and saber type have valid values
+    ///
+    /// Bad notes written by some game versions carry no cut info, for them the bytes are the start of the next note or block instead. This is only a guess, so it is used just when [LoadOptions::detect_bad_notes_without_cut_info] is set
+    pub(crate) fn is_serialized_in(bytes: &[u8]) -> bool {
+        const SABER_TYPE_OFFSET: usize = size_of::<u8>() * 4 + size_of::<ReplayFloat>() * 4;
+
+        bytes.len() >= Self::get_static_size()
+            && bytes[..4].iter().all(|b| *b <= 1)
+            && matches!(
+                ReplayInt::from_le_bytes(
+                    bytes[SABER_TYPE_OFFSET..SABER_TYPE_OFFSET + size_of::<ReplayInt>()]
+                        .try_into()
+                        .unwrap()
+                ),
+                0 | 1
+            )
+    }
+
     pub(crate) fn load<R: Read>(r: &mut R) -> Result<NoteCutInfo> {
         let speed_ok = read_utils::read_bool(r)?;
         let direction_ok = read_utils::read_bool(r)?;
@@ -226,6 +1031,24 @@ impl NoteCutInfo {
             after_cut_rating,
         })
     }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_utils::write_bool(w, self.speed_ok)?;
+        write_utils::write_bool(w, self.direction_ok)?;
+        write_utils::write_bool(w, self.saber_type_ok)?;
+        write_utils::write_bool(w, self.was_cut_too_soon)?;
+        write_utils::write_float(w, self.saber_speed)?;
+        self.saber_dir.write(w)?;
+        write_utils::write_int(w, self.saber_type as ReplayInt)?;
+        write_utils::write_float(w, self.time_deviation)?;
+        write_utils::write_float(w, self.cut_dir_deviation)?;
+        self.cut_point.write(w)?;
+        self.cut_normal.write(w)?;
+        write_utils::write_float(w, self.cut_distance_to_center)?;
+        write_utils::write_float(w, self.cut_angle)?;
+        write_utils::write_float(w, self.before_cut_rating)?;
+        write_utils::write_float(w, self.after_cut_rating)
+    }
 }
 impl GetStaticBlockSize for NoteCutInfo {
     fn get_static_size() -> usize {
@@ -236,13 +1059,44 @@ impl GetStaticBlockSize for NoteCutInfo {
     }
 }
 
+impl ApproxEq for NoteCutInfo {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.speed_ok == other.speed_ok
+            && self.direction_ok == other.direction_ok
+            && self.saber_type_ok == other.saber_type_ok
+            && self.was_cut_too_soon == other.was_cut_too_soon
+            && self.saber_speed.approx_eq(&other.saber_speed)
+            && self.saber_dir.approx_eq(&other.saber_dir)
+            && self.saber_type == other.saber_type
+            && self.time_deviation.approx_eq(&other.time_deviation)
+            && self.cut_dir_deviation.approx_eq(&other.cut_dir_deviation)
+            && self.cut_point.approx_eq(&other.cut_point)
+            && self.cut_normal.approx_eq(&other.cut_normal)
+            && self
+                .cut_distance_to_center
+                .approx_eq(&other.cut_distance_to_center)
+            && self.cut_angle.approx_eq(&other.cut_angle)
+            && self.before_cut_rating.approx_eq(&other.before_cut_rating)
+            && self.after_cut_rating.approx_eq(&other.after_cut_rating)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NoteEventType {
-    Good = 0,
+    Good,
     Bad,
     Miss,
     Bomb,
-    Unknown = 255,
+    /// Event type not known to the crate, contains the raw value stored in the replay file, so it is written back unchanged
+    Unknown(ReplayInt),
+}
+
+impl NoteEventType {
+    /// Returns whether the event breaks the combo, i.e. it is a bad cut or a miss (bomb hits are added by [Note::resets_combo()])
+    pub fn breaks_combo(&self) -> bool {
+        matches!(self, NoteEventType::Bad | NoteEventType::Miss)
+    }
 }
 
 impl TryFrom<ReplayInt> for NoteEventType {
@@ -250,20 +1104,36 @@ impl TryFrom<ReplayInt> for NoteEventType {
 
     fn try_from(v: ReplayInt) -> std::result::Result<Self, Self::Error> {
         match v {
-            x if x == NoteEventType::Good as ReplayInt => Ok(NoteEventType::Good),
-            x if x == NoteEventType::Bad as ReplayInt => Ok(NoteEventType::Bad),
-            x if x == NoteEventType::Miss as ReplayInt => Ok(NoteEventType::Miss),
-            x if x == NoteEventType::Bomb as ReplayInt => Ok(NoteEventType::Bomb),
-            _ => Ok(NoteEventType::Unknown),
+            0 => Ok(NoteEventType::Good),
+            1 => Ok(NoteEventType::Bad),
+            2 => Ok(NoteEventType::Miss),
+            3 => Ok(NoteEventType::Bomb),
+            _ => Ok(NoteEventType::Unknown(v)),
         }
     }
 }
 
+impl From<NoteEventType> for ReplayInt {
+    fn from(event_type: NoteEventType) -> Self {
+        match event_type {
+            NoteEventType::Good => 0,
+            NoteEventType::Bad => 1,
+            NoteEventType::Miss => 2,
+            NoteEventType::Bomb => 3,
+            NoteEventType::Unknown(v) => v,
+        }
+    }
+}
+
+/// Unknown event types are converted to 255
 impl TryInto<u8> for NoteEventType {
     type Error = BsorError;
 
     fn try_into(self) -> std::result::Result<u8, Self::Error> {
-        Ok(self as u8)
+        match self {
+            NoteEventType::Unknown(_) => Ok(u8::MAX),
+            known => Ok(ReplayInt::from(known) as u8),
+        }
     }
 }
 
@@ -273,7 +1143,22 @@ impl PartialEq for NoteEventType {
     }
 }
 
+impl Eq for NoteEventType {}
+
+impl Hash for NoteEventType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+    }
+}
+
+impl fmt::Display for NoteEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NoteScoringType {
     NormalOld = 0,
     Ignore,
@@ -322,7 +1207,22 @@ impl PartialEq for NoteScoringType {
     }
 }
 
+impl Eq for NoteScoringType {}
+
+impl Hash for NoteScoringType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+    }
+}
+
+impl fmt::Display for NoteScoringType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CutDirection {
     TopCenter,
     BottomCenter,
@@ -369,7 +1269,22 @@ impl PartialEq for CutDirection {
     }
 }
 
+impl Eq for CutDirection {}
+
+impl Hash for CutDirection {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+    }
+}
+
+impl fmt::Display for CutDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColorType {
     Red = 0,
     Blue,
@@ -402,12 +1317,44 @@ impl PartialEq for ColorType {
     }
 }
 
+impl Eq for ColorType {}
+
+impl Hash for ColorType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+    }
+}
+
+impl fmt::Display for ColorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests_util::{append_note, generate_random_note, get_notes_buffer};
+    use crate::tests_util::{
+        generate_bomb_with_id, generate_good_note_with_ratings, generate_note_at,
+        generate_random_note, get_notes_buffer, pack_note_id,
+    };
     use std::io::Cursor;
 
+    #[test]
+    fn it_can_access_notes_by_index_and_get_vec() {
+        let items = vec![
+            generate_random_note(NoteEventType::Good),
+            generate_random_note(NoteEventType::Miss),
+        ];
+        let notes = Notes(items.clone());
+
+        assert_eq!(notes.len(), 2);
+        assert!(!notes.is_empty());
+        assert_eq!(notes[1], items[1]);
+        assert_eq!(notes.get_vec(), &items);
+        assert!(Notes(Vec::new()).get_vec().is_empty());
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_note() {
         assert_eq!(Note::get_static_size(), 16);
@@ -418,7 +1365,7 @@ mod tests {
         let note = generate_random_note(NoteEventType::Good);
 
         let mut buf: Vec<u8> = Vec::new();
-        append_note(&mut buf, &note);
+        note.write(&mut buf).unwrap();
 
         let result = Note::load(&mut Cursor::new(buf)).unwrap();
 
@@ -430,7 +1377,7 @@ mod tests {
         let note = generate_random_note(NoteEventType::Bad);
 
         let mut buf: Vec<u8> = Vec::new();
-        append_note(&mut buf, &note);
+        note.write(&mut buf).unwrap();
 
         let result = Note::load(&mut Cursor::new(buf)).unwrap();
 
@@ -442,20 +1389,164 @@ mod tests {
         let note = generate_random_note(NoteEventType::Miss);
 
         let mut buf: Vec<u8> = Vec::new();
-        append_note(&mut buf, &note);
+        note.write(&mut buf).unwrap();
 
         let result = Note::load(&mut Cursor::new(buf)).unwrap();
 
         assert_eq!(result, note)
     }
 
+    #[test]
+    fn it_can_decode_burst_slider_element_note_id() -> Result<()> {
+        let mut buf = ReplayInt::to_le_bytes(73218).to_vec();
+        buf.append(&mut ReplayFloat::to_le_bytes(1.0).to_vec());
+        buf.append(&mut ReplayFloat::to_le_bytes(0.5).to_vec());
+        buf.append(&mut ReplayInt::to_le_bytes(ReplayInt::from(NoteEventType::Miss)).to_vec());
+
+        let result = Note::load(&mut Cursor::new(buf))?;
+
+        assert_eq!(result.scoring_type, NoteScoringType::BurstSliderElement);
+        assert_eq!(result.line_idx, 3);
+        assert_eq!(result.line_layer, 2);
+        assert_eq!(result.color_type, ColorType::Blue);
+        assert_eq!(result.cut_direction, CutDirection::Dot);
+        assert_eq!(result.id(), 73218);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_roundtrip_notes_of_every_scoring_type() -> Result<()> {
+        for scoring_type in [
+            NoteScoringType::NormalOld,
+            NoteScoringType::Ignore,
+            NoteScoringType::NoScore,
+            NoteScoringType::Normal,
+            NoteScoringType::SliderHead,
+            NoteScoringType::SliderTail,
+            NoteScoringType::BurstSliderHead,
+            NoteScoringType::BurstSliderElement,
+            NoteScoringType::Unknown,
+        ] {
+            let mut note = generate_random_note(NoteEventType::Good);
+            note.scoring_type = scoring_type;
+            note.line_idx = 3;
+            note.line_layer = 2;
+            note.color_type = ColorType::Blue;
+            note.cut_direction = CutDirection::BottomRight;
+            note.note_id = pack_note_id(&note);
+
+            let mut buf = Vec::new();
+            note.write(&mut buf)?;
+
+            let result = Note::load(&mut Cursor::new(buf))?;
+
+            assert_eq!(result, note);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_encodes_note_id_from_edited_fields() -> Result<()> {
+        let mut note = generate_random_note(NoteEventType::Miss);
+        note.scoring_type = NoteScoringType::SliderHead;
+        note.line_idx = 0;
+        note.line_layer = 1;
+        note.color_type = ColorType::Red;
+        note.cut_direction = CutDirection::BottomLeft;
+        assert_eq!(note.id(), 40106);
+
+        let mut buf = Vec::new();
+        note.write(&mut buf)?;
+        let result = Note::load(&mut Cursor::new(buf))?;
+
+        assert_eq!(result.note_id, 40106);
+        assert_eq!(result.scoring_type, NoteScoringType::SliderHead);
+        assert_eq!(result.line_layer, 1);
+        assert_eq!(result.cut_direction, CutDirection::BottomLeft);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_writes_back_raw_note_id_of_bomb() -> Result<()> {
+        // BeatLeader stores bombs with color -1, which does not decode into valid enums
+        let mut buf = ReplayInt::to_le_bytes(1999).to_vec();
+        buf.append(&mut ReplayFloat::to_le_bytes(1.0).to_vec());
+        buf.append(&mut ReplayFloat::to_le_bytes(0.5).to_vec());
+        buf.append(&mut ReplayInt::to_le_bytes(ReplayInt::from(NoteEventType::Bomb)).to_vec());
+
+        let note = Note::load(&mut Cursor::new(&buf))?;
+        assert_eq!(note.id(), 1999);
+
+        let mut written = Vec::new();
+        note.write(&mut written)?;
+        assert_eq!(written, buf);
+        assert_eq!(Note::load(&mut Cursor::new(written))?, note);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_writes_back_raw_value_of_unknown_event_type() -> Result<()> {
+        let mut buf = ReplayInt::to_le_bytes(1218).to_vec();
+        buf.append(&mut ReplayFloat::to_le_bytes(1.0).to_vec());
+        buf.append(&mut ReplayFloat::to_le_bytes(0.5).to_vec());
+        buf.append(&mut ReplayInt::to_le_bytes(7).to_vec());
+
+        let note = Note::load(&mut Cursor::new(&buf))?;
+        assert!(matches!(note.event_type, NoteEventType::Unknown(7)));
+
+        let mut written = Vec::new();
+        note.write(&mut written)?;
+        assert_eq!(written, buf);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_decodes_overflowing_scoring_type_as_unknown() -> Result<()> {
+        let mut buf = ReplayInt::to_le_bytes(256 * 10000 + 1213).to_vec();
+        buf.append(&mut ReplayFloat::to_le_bytes(1.0).to_vec());
+        buf.append(&mut ReplayFloat::to_le_bytes(0.5).to_vec());
+        buf.append(&mut ReplayInt::to_le_bytes(ReplayInt::from(NoteEventType::Miss)).to_vec());
+
+        let result = Note::load(&mut Cursor::new(buf))?;
+
+        assert_eq!(result.scoring_type, NoteScoringType::Unknown);
+        assert_eq!(result.line_idx, 1);
+        assert_eq!(result.line_layer, 2);
+        assert_eq!(result.color_type, ColorType::Blue);
+        assert_eq!(result.cut_direction, CutDirection::MiddleRight);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_write_notes() -> Result<()> {
+        let notes = Vec::from([
+            generate_random_note(NoteEventType::Good),
+            generate_random_note(NoteEventType::Bad),
+            generate_random_note(NoteEventType::Miss),
+            generate_random_note(NoteEventType::Bomb),
+        ]);
+
+        let mut buf = Vec::new();
+        Notes::new(notes.clone()).write(&mut buf)?;
+
+        assert_eq!(buf, get_notes_buffer(&notes)?);
+
+        Ok(())
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_notes() {
         assert_eq!(Notes::get_static_size(), 5);
     }
 
     #[test]
-    fn it_returns_invalid_bsor_error_when_notes_block_id_is_invalid() -> Result<()> {
+    fn it_returns_unexpected_block_error_when_notes_block_id_is_invalid() -> Result<()> {
         let notes = Vec::from([
             generate_random_note(NoteEventType::Bomb),
             generate_random_note(NoteEventType::Good),
@@ -466,7 +1557,10 @@ mod tests {
 
         let result = Notes::load(&mut Cursor::new(buf));
 
-        assert!(matches!(result, Err(BsorError::InvalidBsor)));
+        assert!(matches!(
+            result,
+            Err(BsorError::UnexpectedBlock { found: 255, .. })
+        ));
 
         Ok(())
     }
@@ -516,4 +1610,682 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn it_can_classify_stream_notes() {
+        let mut vec = Vec::new();
+        for i in 0..8 {
+            let color = if i % 2 == 0 {
+                ColorType::Red
+            } else {
+                ColorType::Blue
+            };
+            vec.push(generate_note_at(
+                NoteEventType::Good,
+                color,
+                i as ReplayTime * 0.125,
+            ));
+        }
+        let notes = Notes::new(vec);
+
+        let result = notes.pattern_segments();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].kind, PatternKind::Stream);
+        assert_eq!(result[0].note_indices, (0..8).collect::<Vec<_>>());
+        assert_eq!(result[0].start_time, 0.0);
+        assert_eq!(result[0].end_time, 0.875);
+    }
+
+    #[test]
+    fn it_can_classify_jumps_and_other_notes() {
+        let notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.5),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 1.5),
+            generate_note_at(NoteEventType::Bomb, ColorType::Red, 1.75),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 2.0),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 3.0),
+        ]));
+
+        let result = notes.pattern_segments();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].kind, PatternKind::Jump);
+        assert_eq!(result[0].note_indices, vec![0, 1, 2, 3]);
+        assert_eq!(result[1].kind, PatternKind::Other);
+        assert_eq!(result[1].note_indices, vec![5, 6]);
+    }
+
+    #[test]
+    fn it_can_compute_combo_by_color() {
+        let notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 1.5),
+            generate_note_at(NoteEventType::Miss, ColorType::Red, 2.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 2.5),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 3.0),
+            generate_note_at(NoteEventType::Bomb, ColorType::Blue, 3.25),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 3.5),
+            generate_note_at(NoteEventType::Bad, ColorType::Red, 4.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 4.5),
+        ]));
+
+        assert_eq!(notes.combo_by_color(), (1, 2));
+    }
+
+    #[test]
+    fn it_can_iterate_over_same_color_pairs() {
+        let notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 1.5),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 2.0),
+            generate_note_at(NoteEventType::Bomb, ColorType::Red, 2.25),
+            generate_note_at(NoteEventType::Miss, ColorType::Red, 2.5),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 3.0),
+        ]));
+
+        let red_pairs = notes.same_color_pairs(ColorType::Red).collect::<Vec<_>>();
+
+        assert_eq!(red_pairs.len(), 2);
+        assert_eq!(red_pairs[0], (&notes[0], &notes[2]));
+        assert_eq!(red_pairs[1], (&notes[2], &notes[4]));
+        assert_eq!(notes.same_color_pairs(ColorType::Blue).count(), 1);
+    }
+
+    #[test]
+    fn it_can_compute_saber_speed_stats() {
+        let mut vec = Vec::new();
+        for (event_type, speed) in [
+            (NoteEventType::Good, 10.0),
+            (NoteEventType::Good, 20.0),
+            (NoteEventType::Bad, 100.0),
+            (NoteEventType::Miss, 0.0),
+            (NoteEventType::Good, 45.0),
+        ] {
+            let mut note = generate_random_note(event_type);
+            if let Some(cut_info) = note.cut_info.as_mut() {
+                cut_info.saber_speed = speed;
+            }
+            vec.push(note);
+        }
+        let notes = Notes::new(vec);
+
+        assert_eq!(notes.saber_speed_stats(), (10.0, 45.0, 25.0));
+        assert_eq!(Notes::new(Vec::new()).saber_speed_stats(), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn it_can_compute_time_at_full_multiplier() {
+        let notes_with_miss_at = |miss_idx: Option<usize>| {
+            Notes::new(
+                (0..20)
+                    .map(|i| {
+                        let event_type = if Some(i) == miss_idx {
+                            NoteEventType::Miss
+                        } else {
+                            NoteEventType::Good
+                        };
+                        generate_note_at(event_type, ColorType::Red, i as ReplayTime)
+                    })
+                    .collect(),
+            )
+        };
+
+        assert_eq!(notes_with_miss_at(None).time_at_full_multiplier(), 6.0);
+        assert_eq!(notes_with_miss_at(Some(3)).time_at_full_multiplier(), 2.0);
+        // 8x is reached at the 14th good cut (time 13) and lost by the miss at time 15, the 4 remaining cuts are not enough to regain it
+        assert_eq!(notes_with_miss_at(Some(15)).time_at_full_multiplier(), 2.0);
+    }
+
+    #[test]
+    fn it_can_match_notes_to_map() {
+        let notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 1.5),
+            generate_note_at(NoteEventType::Miss, ColorType::Red, 2.0),
+        ]));
+
+        let expected = [
+            (notes[0].id(), 1.005),
+            (notes[2].id(), 2.0),
+            (notes[1].id(), 3.0),
+        ];
+
+        let result = notes.match_to_map(&expected);
+
+        assert_eq!(
+            result,
+            vec![
+                MatchResult::Matched {
+                    note_idx: 0,
+                    expected_idx: 0
+                },
+                MatchResult::UnmatchedNote { note_idx: 1 },
+                MatchResult::Matched {
+                    note_idx: 2,
+                    expected_idx: 1
+                },
+                MatchResult::UnmatchedExpected { expected_idx: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_can_compute_perfect_cut_ratio() {
+        let mut ignored = generate_good_note_with_ratings(1.0, 1.0, 0.0);
+        ignored.scoring_type = NoteScoringType::NoScore;
+
+        let notes = Notes::new(Vec::from([
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_good_note_with_ratings(0.9, 1.0, 0.0),
+            generate_good_note_with_ratings(1.0, 1.0, 0.1),
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_random_note(NoteEventType::Miss),
+            generate_random_note(NoteEventType::Bomb),
+            ignored,
+        ]));
+
+        assert_eq!(notes.perfect_cut_ratio(), 0.5);
+        assert_eq!(Notes::new(Vec::new()).perfect_cut_ratio(), 0.0);
+    }
+
+    #[test]
+    fn it_can_group_notes_into_time_buckets() {
+        let notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 0.5),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 1.9),
+            generate_note_at(NoteEventType::Miss, ColorType::Red, 6.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 2.0),
+        ]));
+
+        let buckets = notes.time_buckets(2.0);
+
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets[0].0, 0.0);
+        assert_eq!(buckets[0].1, vec![&notes[0], &notes[1]]);
+        assert_eq!(buckets[1].0, 2.0);
+        assert_eq!(buckets[1].1, vec![&notes[3]]);
+        assert_eq!(buckets[2].0, 6.0);
+        assert_eq!(buckets[2].1, vec![&notes[2]]);
+        assert!(notes.time_buckets(0.0).is_empty());
+    }
+
+    #[test]
+    fn it_can_compute_swing_speed_stddev() {
+        let mut notes = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+            .iter()
+            .enumerate()
+            .map(|(i, speed)| {
+                let mut note =
+                    generate_note_at(NoteEventType::Good, ColorType::Red, i as ReplayTime);
+                note.cut_info.as_mut().unwrap().saber_speed = *speed;
+
+                note
+            })
+            .collect::<Vec<_>>();
+        let mut blue = generate_note_at(NoteEventType::Good, ColorType::Blue, 10.0);
+        blue.cut_info.as_mut().unwrap().saber_speed = 100.0;
+        notes.push(blue);
+        notes.push(generate_note_at(NoteEventType::Miss, ColorType::Red, 11.0));
+
+        let notes = Notes::new(notes);
+
+        assert!((notes.swing_speed_stddev(ColorType::Red) - 2.0).abs() < 0.0001);
+        assert_eq!(notes.swing_speed_stddev(ColorType::Blue), 0.0);
+        assert_eq!(
+            Notes::new(Vec::new()).swing_speed_stddev(ColorType::Red),
+            0.0
+        );
+    }
+
+    #[test]
+    fn it_can_compute_max_possible_score() {
+        let notes = (0..14)
+            .map(|i| generate_note_at(NoteEventType::Miss, ColorType::Red, i as ReplayTime))
+            .chain([generate_note_at(NoteEventType::Bomb, ColorType::Red, 15.0)])
+            .collect::<Vec<_>>();
+
+        assert_eq!(Notes::new(notes[..1].to_vec()).max_possible_score(), 115);
+        assert_eq!(
+            Notes::new(notes).max_possible_score(),
+            115 * (1 + 4 * 2 + 8 * 4 + 8)
+        );
+        assert_eq!(Notes::new(Vec::new()).max_possible_score(), 0);
+    }
+
+    #[test]
+    fn it_can_compute_note_score() {
+        let note = |event_type, scoring_type, before: f32, after: f32, distance: f32| {
+            let mut note = generate_random_note(event_type);
+            note.scoring_type = scoring_type;
+            if let Some(cut_info) = note.cut_info.as_mut() {
+                cut_info.before_cut_rating = before;
+                cut_info.after_cut_rating = after;
+                cut_info.cut_distance_to_center = distance;
+            }
+
+            note
+        };
+        let score = |pre_swing, post_swing, accuracy| {
+            Some(NoteScore {
+                pre_swing,
+                post_swing,
+                accuracy,
+                total: pre_swing + post_swing + accuracy,
+            })
+        };
+
+        let good = NoteEventType::Good;
+        assert_eq!(
+            note(good, NoteScoringType::Normal, 0.5, 1.2, 0.06).score(),
+            score(35, 30, 12)
+        );
+        assert_eq!(
+            note(good, NoteScoringType::Normal, 1.0, 1.0, 0.0).score(),
+            score(70, 30, 15)
+        );
+        assert_eq!(
+            note(good, NoteScoringType::Normal, -0.5, 0.0, 0.4).score(),
+            score(0, 0, 0)
+        );
+        assert_eq!(
+            note(good, NoteScoringType::SliderHead, 1.0, 0.0, 0.0).score(),
+            score(70, 30, 15)
+        );
+        assert_eq!(
+            note(good, NoteScoringType::BurstSliderElement, 1.0, 1.0, 0.0).score(),
+            score(0, 0, 20)
+        );
+        assert_eq!(
+            note(good, NoteScoringType::Normal, 1.0, 1.0, 0.0)
+                .score()
+                .map(|s| s.total),
+            Some(115)
+        );
+
+        for event_type in [NoteEventType::Bad, NoteEventType::Miss, NoteEventType::Bomb] {
+            assert_eq!(
+                note(event_type, NoteScoringType::Normal, 1.0, 1.0, 0.0).score(),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn it_can_check_whether_note_event_breaks_combo() {
+        assert!(!NoteEventType::Good.breaks_combo());
+        assert!(NoteEventType::Bad.breaks_combo());
+        assert!(NoteEventType::Miss.breaks_combo());
+        assert!(!NoteEventType::Bomb.breaks_combo());
+        assert!(!NoteEventType::Unknown(4).breaks_combo());
+    }
+
+    #[test]
+    fn it_can_check_whether_note_resets_combo() {
+        assert!(!generate_random_note(NoteEventType::Good).resets_combo());
+        assert!(generate_random_note(NoteEventType::Bad).resets_combo());
+        assert!(generate_random_note(NoteEventType::Miss).resets_combo());
+        assert!(generate_random_note(NoteEventType::Bomb).resets_combo());
+        assert!(!generate_random_note(NoteEventType::Unknown(4)).resets_combo());
+    }
+
+    #[test]
+    fn it_can_serialize_notes_to_bytes() -> Result<()> {
+        let notes = Notes::new(Vec::from([
+            generate_random_note(NoteEventType::Good),
+            generate_bomb_with_id(1999),
+            generate_random_note(NoteEventType::Miss),
+        ]));
+        let buf = notes.to_bytes()?;
+
+        let bytes = Notes::load(&mut Cursor::new(&buf))?.to_bytes()?;
+
+        assert_eq!(bytes, buf);
+        assert_eq!(Notes::load(&mut Cursor::new(bytes))?, notes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_compute_cut_point_centroid_and_spread() {
+        let mut notes = Vec::new();
+        for (x, y) in [(0.1, 0.2), (0.3, 0.2), (0.2, 0.1), (0.2, 0.3)] {
+            let mut note = generate_random_note(NoteEventType::Good);
+            note.cut_info.as_mut().unwrap().cut_point = Vector3 { x, y, z: 0.0 };
+            notes.push(note);
+        }
+        let mut bad = generate_random_note(NoteEventType::Bad);
+        bad.cut_info.as_mut().unwrap().cut_point = Vector3 {
+            x: 5.0,
+            y: 5.0,
+            z: 5.0,
+        };
+        notes.push(bad);
+        notes.push(generate_random_note(NoteEventType::Miss));
+        let notes = Notes::new(notes);
+
+        let centroid = notes.cut_point_centroid().unwrap();
+        assert!((centroid.x - 0.2).abs() < 1e-6);
+        assert!((centroid.y - 0.2).abs() < 1e-6);
+        assert!(centroid.z.abs() < 1e-6);
+        assert!((notes.cut_point_spread().unwrap() - 0.1).abs() < 1e-6);
+
+        assert_eq!(Notes::new(Vec::new()).cut_point_centroid(), None);
+        assert_eq!(Notes::new(Vec::new()).cut_point_spread(), None);
+    }
+
+    #[test]
+    fn it_can_find_note_near_time() {
+        let notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 2.0),
+            generate_note_at(NoteEventType::Miss, ColorType::Red, 3.0),
+        ]));
+
+        assert_eq!(notes.note_near_time(1.9, 0.2), Some(&notes[1]));
+        assert_eq!(notes.note_near_time(2.05, 0.1), Some(&notes[1]));
+        assert_eq!(notes.note_near_time(0.5, 0.6), Some(&notes[0]));
+        assert_eq!(notes.note_near_time(2.5, 0.1), None);
+        assert_eq!(notes.note_near_time(4.0, 0.5), None);
+        assert_eq!(Notes::new(Vec::new()).note_near_time(1.0, 1.0), None);
+    }
+
+    #[test]
+    fn it_can_find_implausible_cuts() {
+        let mut out_of_range = generate_good_note_with_ratings(1.0, 1.0, 0.0);
+        out_of_range.cut_info.as_mut().unwrap().before_cut_rating = -0.5;
+        let mut not_finite = generate_good_note_with_ratings(1.0, 1.0, 0.0);
+        not_finite.cut_info.as_mut().unwrap().saber_speed = ReplayFloat::NAN;
+
+        let notes = Notes::new(Vec::from([
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            out_of_range,
+            generate_good_note_with_ratings(1.3, 1.0, 0.2),
+            generate_random_note(NoteEventType::Miss),
+            not_finite,
+        ]));
+
+        assert!(notes[0].cut_info.as_ref().unwrap().is_plausible());
+        assert!(!notes[1].cut_info.as_ref().unwrap().is_plausible());
+        assert_eq!(notes.implausible_cuts(), vec![1, 4]);
+    }
+
+    #[test]
+    fn it_detects_bad_notes_without_cut_info() -> Result<()> {
+        let mut bad = generate_random_note(NoteEventType::Bad);
+        bad.cut_info = None;
+
+        for notes in [
+            vec![
+                generate_random_note(NoteEventType::Good),
+                bad.clone(),
+                generate_random_note(NoteEventType::Miss),
+                generate_random_note(NoteEventType::Bad),
+            ],
+            vec![generate_random_note(NoteEventType::Good), bad],
+        ] {
+            let notes = Notes::new(notes);
+
+            let mut buf = Vec::new();
+            notes.write(&mut buf)?;
+            buf.append(&mut Vec::from([BlockType::Walls.try_into()?, 0, 0, 0, 0]));
+
+            let result = Notes::load_detecting_cut_info(
+                &mut PeekReader::new(Cursor::new(&buf)),
+                0,
+                &LoadOptions::default(),
+                &mut Vec::new(),
+            )?;
+            assert_eq!(result, notes);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_compute_cut_direction_histogram() {
+        let mut notes = Vec::new();
+        for (event_type, cut_direction) in [
+            (NoteEventType::Good, CutDirection::Dot),
+            (NoteEventType::Good, CutDirection::BottomCenter),
+            (NoteEventType::Good, CutDirection::Dot),
+            (NoteEventType::Miss, CutDirection::TopCenter),
+            (NoteEventType::Bad, CutDirection::Dot),
+            (NoteEventType::Good, CutDirection::BottomCenter),
+            (NoteEventType::Good, CutDirection::Dot),
+        ] {
+            let mut note = generate_random_note(event_type);
+            note.cut_direction = cut_direction;
+            notes.push(note);
+        }
+
+        let histogram = Notes::new(notes).cut_direction_histogram();
+
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(histogram[&CutDirection::Dot], 3);
+        assert_eq!(histogram[&CutDirection::BottomCenter], 2);
+        assert_eq!(histogram.get(&CutDirection::TopCenter), None);
+    }
+
+    #[test]
+    fn it_can_find_notes_by_id() {
+        let mut notes = Vec::new();
+        for (line_idx, cut_direction) in [
+            (1, CutDirection::TopCenter),
+            (2, CutDirection::Dot),
+            (1, CutDirection::TopCenter),
+        ] {
+            let mut note = generate_random_note(NoteEventType::Good);
+            note.scoring_type = NoteScoringType::Normal;
+            note.line_idx = line_idx;
+            note.line_layer = 0;
+            note.color_type = ColorType::Blue;
+            note.cut_direction = cut_direction;
+            note.note_id = pack_note_id(&note);
+            notes.push(note);
+        }
+        notes.push(generate_bomb_with_id(1999));
+        let notes = Notes::new(notes);
+
+        let found = notes.find_by_id(31010);
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|n| n.line_idx == 1));
+
+        assert_eq!(notes.find_by_id(32018).len(), 1);
+        assert_eq!(notes.find_by_id(1999).len(), 1);
+        assert!(notes.find_by_id(12345).is_empty());
+    }
+
+    #[test]
+    fn it_can_compute_pre_break_accuracy() {
+        let mut notes = Vec::from([
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_good_note_with_ratings(1.0, 1.0, 0.3),
+            generate_random_note(NoteEventType::Miss),
+            generate_good_note_with_ratings(0.0, 0.0, 0.3),
+        ]);
+        for note in notes.iter_mut() {
+            note.scoring_type = NoteScoringType::Normal;
+        }
+        let notes = Notes::new(notes);
+
+        let expected = (1.0 + 100.0 / 115.0) / 2.0;
+        assert!((notes.pre_break_accuracy() - expected).abs() < 0.0001);
+
+        let mut broken_first = Vec::from([generate_random_note(NoteEventType::Bad)]);
+        broken_first.extend(notes.iter().cloned());
+        assert_eq!(Notes::new(broken_first).pre_break_accuracy(), 0.0);
+
+        let mut bomb_first = Vec::from([generate_bomb_with_id(1999)]);
+        bomb_first.extend(notes.iter().cloned());
+        assert_eq!(Notes::new(bomb_first).pre_break_accuracy(), 0.0);
+    }
+
+    #[test]
+    fn it_can_find_note_at_time() {
+        let notes = Notes::new(
+            [1.0, 2.0, 2.05, 3.0]
+                .iter()
+                .map(|t| generate_note_at(NoteEventType::Good, ColorType::Red, *t))
+                .collect(),
+        );
+
+        assert_eq!(notes.find_index_at_time(2.0), Some(1));
+        assert_eq!(notes.find_index_at_time(2.04), Some(2));
+        assert_eq!(notes.find_index_at_time(0.95), Some(0));
+        assert_eq!(notes.find_index_at_time(3.08), Some(3));
+        assert_eq!(notes.find_index_at_time(2.5), None);
+        assert_eq!(notes.find_at_time(1.02).map(|n| n.event_time), Some(1.0));
+        assert!(notes.find_at_time(5.0).is_none());
+        assert!(Notes::new(Vec::new()).find_at_time(1.0).is_none());
+    }
+
+    #[test]
+    fn it_can_find_last_good_note_before_time() {
+        let notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 2.0),
+            generate_note_at(NoteEventType::Miss, ColorType::Red, 2.5),
+            generate_note_at(NoteEventType::Bad, ColorType::Blue, 2.8),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 3.0),
+        ]));
+
+        let last_good = |t| notes.last_good_before(t).map(|n| n.event_time);
+
+        assert_eq!(last_good(1.5), Some(1.0));
+        assert_eq!(last_good(2.0), Some(1.0));
+        assert_eq!(last_good(2.9), Some(2.0));
+        assert_eq!(last_good(4.0), Some(3.0));
+        assert_eq!(last_good(1.0), None);
+    }
+
+    #[test]
+    fn it_can_compute_accuracy() {
+        let mut notes = Vec::from([
+            generate_good_note_with_ratings(1.0, 1.0, 0.0),
+            generate_good_note_with_ratings(0.5, 1.0, 0.0),
+            generate_good_note_with_ratings(1.0, 0.5, 0.3),
+            generate_random_note(NoteEventType::Bad),
+            generate_random_note(NoteEventType::Miss),
+        ]);
+        for note in notes.iter_mut() {
+            note.scoring_type = NoteScoringType::Normal;
+        }
+        let mut bomb = generate_random_note(NoteEventType::Bomb);
+        bomb.scoring_type = NoteScoringType::NoScore;
+        notes.push(bomb);
+        let notes = Notes::new(notes);
+
+        let (accuracy, achieved, max) = notes.accuracy();
+
+        assert_eq!(achieved, 115 + 80 + 85);
+        assert_eq!(max, 115 * 5);
+        assert!((accuracy - 280.0 / 575.0).abs() < 0.0001);
+        assert_eq!(Notes::new(Vec::new()).accuracy(), (0.0, 0, 0));
+    }
+
+    #[test]
+    fn it_can_compute_max_combo_and_combo_breaks() {
+        let notes = Notes::new(Vec::from([
+            generate_note_at(NoteEventType::Good, ColorType::Red, 1.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 2.0),
+            generate_note_at(NoteEventType::Miss, ColorType::Red, 3.0),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 4.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 5.0),
+            generate_note_at(NoteEventType::Good, ColorType::Red, 6.0),
+            generate_note_at(NoteEventType::Bomb, ColorType::Red, 7.0),
+            generate_note_at(NoteEventType::Good, ColorType::Blue, 8.0),
+            generate_note_at(NoteEventType::Bad, ColorType::Blue, 9.0),
+        ]));
+
+        assert_eq!(notes.max_combo(), 3);
+
+        let break_times: Vec<_> = notes.combo_breaks().iter().map(|n| n.event_time).collect();
+        assert_eq!(break_times, vec![3.0, 7.0, 9.0]);
+
+        assert_eq!(Notes::new(Vec::new()).max_combo(), 0);
+    }
+
+    #[test]
+    fn it_can_count_note_events() {
+        let notes = Notes::new(Vec::from([
+            generate_random_note(NoteEventType::Good),
+            generate_random_note(NoteEventType::Good),
+            generate_random_note(NoteEventType::Good),
+            generate_random_note(NoteEventType::Bad),
+            generate_random_note(NoteEventType::Miss),
+            generate_random_note(NoteEventType::Miss),
+            generate_random_note(NoteEventType::Bomb),
+            generate_random_note(NoteEventType::Unknown(4)),
+        ]));
+
+        assert_eq!(
+            notes.event_counts(),
+            NoteCounts {
+                good: 3,
+                bad: 1,
+                miss: 2,
+                bomb: 1,
+                unknown: 1,
+            }
+        );
+        assert_eq!(Notes::new(Vec::new()).event_counts(), NoteCounts::default());
+    }
+
+    #[test]
+    fn it_can_display_note_enums() {
+        let event_types = [
+            (NoteEventType::Good, "Good"),
+            (NoteEventType::Bad, "Bad"),
+            (NoteEventType::Miss, "Miss"),
+            (NoteEventType::Bomb, "Bomb"),
+            (NoteEventType::Unknown(4), "Unknown(4)"),
+        ];
+        for (event_type, expected) in event_types {
+            assert_eq!(event_type.to_string(), expected);
+        }
+
+        let scoring_types = [
+            (NoteScoringType::NormalOld, "NormalOld"),
+            (NoteScoringType::Ignore, "Ignore"),
+            (NoteScoringType::NoScore, "NoScore"),
+            (NoteScoringType::Normal, "Normal"),
+            (NoteScoringType::SliderHead, "SliderHead"),
+            (NoteScoringType::SliderTail, "SliderTail"),
+            (NoteScoringType::BurstSliderHead, "BurstSliderHead"),
+            (NoteScoringType::BurstSliderElement, "BurstSliderElement"),
+            (NoteScoringType::Unknown, "Unknown"),
+        ];
+        for (scoring_type, expected) in scoring_types {
+            assert_eq!(scoring_type.to_string(), expected);
+        }
+
+        let cut_directions = [
+            (CutDirection::TopCenter, "TopCenter"),
+            (CutDirection::BottomCenter, "BottomCenter"),
+            (CutDirection::MiddleLeft, "MiddleLeft"),
+            (CutDirection::MiddleRight, "MiddleRight"),
+            (CutDirection::TopLeft, "TopLeft"),
+            (CutDirection::TopRight, "TopRight"),
+            (CutDirection::BottomLeft, "BottomLeft"),
+            (CutDirection::BottomRight, "BottomRight"),
+            (CutDirection::Dot, "Dot"),
+            (CutDirection::Unknown, "Unknown"),
+        ];
+        for (cut_direction, expected) in cut_directions {
+            assert_eq!(cut_direction.to_string(), expected);
+        }
+
+        let colors = [
+            (ColorType::Red, "Red"),
+            (ColorType::Blue, "Blue"),
+            (ColorType::Unknown, "Unknown"),
+        ];
+        for (color, expected) in colors {
+            assert_eq!(color.to_string(), expected);
+        }
+    }
 }
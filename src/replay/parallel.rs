@@ -0,0 +1,96 @@
+//! Parallel loading of indexed replay blocks (requires `rayon` feature)
+//!
+//! # Example
+//! ```no_run
+//! use bsor::prelude::*;
+//! use std::path::Path;
+//!
+//! let path = Path::new("example.bsor");
+//! let replay_index = ReplayIndex::from_path(path).unwrap();
+//!
+//! let replay = replay_index.load_all_parallel(path).unwrap();
+//! println!("{} notes", replay.notes.len());
+//! ```
+use super::{LoadBlock, Replay, ReplayIndex, Result};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+impl ReplayIndex {
+    /// Loads all blocks of the replay file the index was created from in parallel, each block using its own file handle
+    pub fn load_all_parallel(&self, path: &Path) -> Result<Replay> {
+        let (frames, notes, walls, heights, pauses) = (
+            &self.frames,
+            &self.notes,
+            &self.walls,
+            &self.heights,
+            &self.pauses,
+        );
+
+        let ((frames, notes), (walls, (heights, pauses))) = rayon::join(
+            || {
+                rayon::join(
+                    || load_from_file(path, frames),
+                    || load_from_file(path, notes),
+                )
+            },
+            || {
+                rayon::join(
+                    || load_from_file(path, walls),
+                    || {
+                        rayon::join(
+                            || load_from_file(path, heights),
+                            || load_from_file(path, pauses),
+                        )
+                    },
+                )
+            },
+        );
+
+        Ok(Replay {
+            version: self.version,
+            info: self.info.clone(),
+            frames: frames?,
+            notes: notes?,
+            walls: walls?,
+            heights: heights?,
+            pauses: pauses?,
+        })
+    }
+}
+
+fn load_from_file<B: LoadBlock>(path: &Path, block: &B) -> Result<B::Item> {
+    let r = &mut BufReader::new(File::open(path)?);
+
+    block.load(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_util::{generate_random_replay, get_replay_buffer};
+    use std::io::Cursor;
+
+    #[test]
+    fn it_can_load_all_blocks_in_parallel() -> Result<()> {
+        let buf = get_replay_buffer(&generate_random_replay())?;
+        let path = std::env::temp_dir().join(format!("bsor-parallel-{}.bsor", std::process::id()));
+        std::fs::write(&path, &buf)?;
+
+        let index = ReplayIndex::index(&mut Cursor::new(buf.clone()))?;
+        let result = index.load_all_parallel(&path);
+        std::fs::remove_file(&path)?;
+        let result = result?;
+
+        let replay = Replay::load(&mut Cursor::new(buf))?;
+        assert_eq!(result.version, replay.version);
+        assert_eq!(result.info, replay.info);
+        assert_eq!(result.frames, replay.frames);
+        assert_eq!(result.notes, replay.notes);
+        assert_eq!(result.walls, replay.walls);
+        assert_eq!(result.heights, replay.heights);
+        assert_eq!(result.pauses, replay.pauses);
+
+        Ok(())
+    }
+}
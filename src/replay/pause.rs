@@ -1,42 +1,72 @@
 //! structs storing the Pauses block data
-use super::{read_utils, BsorError, ReplayTime, Result};
+use super::{read_utils, write_utils, ReplayTime, Result};
 use crate::replay::{
-    assert_start_of_block, BlockIndex, BlockType, GetStaticBlockSize, LoadBlock, LoadRealBlockSize,
-    ReplayFloat, ReplayInt, ReplayLong,
+    expect_block, load_block_items, write_block_items, ApproxEq, BlockIndex, BlockType,
+    GetStaticBlockSize, LoadBlock, LoadOptions, LoadRealBlockSize, LoadWarning, ReplayFloat,
+    ReplayInt, ReplayLong,
 };
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::marker::PhantomData;
 use std::mem::size_of;
 use std::ops::Deref;
 
 /// Struct implements [std::ops::Deref] trait so it could be treated as Vec<[Pause]>
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pauses(Vec<Pause>);
 
 impl Pauses {
-    #[cfg(test)]
-    pub(crate) fn new(vec: Vec<Pause>) -> Pauses {
+    /// Creates block from given items
+    pub fn new(vec: Vec<Pause>) -> Pauses {
         Pauses(vec)
     }
 
-    pub(crate) fn load<R: Read>(r: &mut R) -> Result<Pauses> {
-        match read_utils::read_byte(r) {
-            Ok(v) => {
-                if v != 5 {
-                    return Err(BsorError::InvalidBsor);
+    /// Returns pauses with entries whose song times are within `gap` of the previous entry merged into one, keeping the earliest time and summing durations
+    pub fn coalesced(&self, gap: ReplayTime) -> Vec<Pause> {
+        let mut result: Vec<Pause> = Vec::with_capacity(self.len());
+        let mut last_time = None;
+
+        for pause in self.iter() {
+            match (result.last_mut(), last_time) {
+                (Some(merged), Some(last_time)) if pause.time - last_time <= gap => {
+                    merged.duration = merged.duration.saturating_add(pause.duration);
                 }
+                _ => result.push(pause.clone()),
             }
-            Err(e) => return Err(e),
+
+            last_time = Some(pause.time);
         }
 
-        let count = read_utils::read_int(r)? as usize;
-        let mut vec = Vec::<Pause>::with_capacity(count);
+        result
+    }
 
-        for _ in 0..count {
-            vec.push(Pause::load(r)?);
-        }
+    /// Returns total duration of all pauses in whole seconds, negative durations are treated as zero like in [Pause::duration_ms()]
+    pub fn total_duration(&self) -> ReplayLong {
+        self.iter().fold(0, |total: ReplayLong, p| {
+            total.saturating_add(p.duration.max(0))
+        })
+    }
 
-        Ok(Pauses(vec))
+    pub(crate) fn load<RS: Read + Seek>(r: &mut RS) -> Result<Pauses> {
+        let offset = r.stream_position()?;
+
+        Self::load_with_options(r, offset, &LoadOptions::default(), &mut Vec::new())
+    }
+
+    pub(crate) fn load_with_options<R: Read>(
+        r: &mut R,
+        offset: u64,
+        options: &LoadOptions,
+        warnings: &mut Vec<LoadWarning>,
+    ) -> Result<Pauses> {
+        Ok(Pauses(load_block_items(
+            r,
+            BlockType::Pauses,
+            offset,
+            options,
+            warnings,
+            Pause::load,
+        )?))
     }
 
     pub(crate) fn load_block<RS: Read + Seek>(
@@ -47,6 +77,11 @@ impl Pauses {
 
         Self::load(r)
     }
+
+    /// Writes the block id, items count and all items
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_block_items(w, BlockType::Pauses, &self.0, Pause::write)
+    }
 }
 
 impl Deref for Pauses {
@@ -66,17 +101,19 @@ impl GetStaticBlockSize for Pauses {
 impl LoadBlock for BlockIndex<Pauses> {
     type Item = Pauses;
 
-    /// Loads Frames block from ReplayIndex
+    /// Loads Pauses block from ReplayIndex
     fn load<RS: Read + Seek>(&self, r: &mut RS) -> Result<Self::Item> {
         Self::Item::load_block(r, self)
     }
 }
 
+impl_block!(Pauses, Pause, [time]);
+
 impl LoadRealBlockSize for Pauses {
     type Item = Pauses;
 
     fn load_real_block_size<RS: Read + Seek>(r: &mut RS, pos: u64) -> Result<BlockIndex<Pauses>> {
-        assert_start_of_block(r, BlockType::Pauses)?;
+        expect_block(r, BlockType::Pauses, pos)?;
 
         let count = read_utils::read_int(r)?;
 
@@ -90,19 +127,38 @@ impl LoadRealBlockSize for Pauses {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Pause {
+    /// pause duration in whole seconds (wall-clock time the game was paused)
     pub duration: ReplayLong,
+    /// song time the game was paused at
     pub time: ReplayTime,
 }
 
 impl Pause {
     pub(crate) fn load<R: Read>(r: &mut R) -> Result<Pause> {
-        let duration = read_utils::read_long(r)? as ReplayLong;
+        let duration = read_utils::read_long(r)?;
         let time = read_utils::read_float(r)?;
 
         Ok(Self { duration, time })
     }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_utils::write_long(w, self.duration)?;
+        write_utils::write_float(w, self.time)
+    }
+
+    /// Returns pause duration in milliseconds
+    ///
+    /// Duration is stored in whole seconds as a signed long, so the result has a one second resolution; negative (corrupted) durations are treated as 0
+    pub fn duration_ms(&self) -> u64 {
+        (self.duration.max(0) as u64).saturating_mul(1000)
+    }
 }
 
 impl GetStaticBlockSize for Pause {
@@ -111,6 +167,12 @@ impl GetStaticBlockSize for Pause {
     }
 }
 
+impl ApproxEq for Pause {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.duration == other.duration && self.time.approx_eq(&other.time)
+    }
+}
+
 impl LoadRealBlockSize for Pause {
     type Item = Pause;
 }
@@ -118,9 +180,22 @@ impl LoadRealBlockSize for Pause {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::replay::BsorError;
     use crate::tests_util::{append_pause, generate_random_pause, get_pauses_buffer};
     use std::io::Cursor;
 
+    #[test]
+    fn it_can_access_pauses_by_index_and_get_vec() {
+        let items = vec![generate_random_pause(), generate_random_pause()];
+        let pauses = Pauses(items.clone());
+
+        assert_eq!(pauses.len(), 2);
+        assert!(!pauses.is_empty());
+        assert_eq!(pauses[1], items[1]);
+        assert_eq!(pauses.get_vec(), &items);
+        assert!(Pauses(Vec::new()).get_vec().is_empty());
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_pause() {
         assert_eq!(Pause::get_static_size(), 12);
@@ -138,13 +213,65 @@ mod tests {
         assert_eq!(result, pause)
     }
 
+    #[test]
+    fn it_can_load_pause_with_large_duration() {
+        let pause = Pause {
+            duration: 1 << 40,
+            time: 12.5,
+        };
+
+        let mut buf: Vec<u8> = Vec::new();
+        append_pause(&mut buf, &pause);
+
+        let result = Pause::load(&mut Cursor::new(buf)).unwrap();
+
+        assert_eq!(result.duration, 1 << 40);
+        assert_eq!(result.duration_ms(), (1 << 40) * 1000);
+    }
+
+    #[test]
+    fn it_treats_negative_pause_duration_as_zero() {
+        let pause = Pause {
+            duration: -5,
+            time: 12.5,
+        };
+
+        let mut buf: Vec<u8> = Vec::new();
+        append_pause(&mut buf, &pause);
+
+        let result = Pause::load(&mut Cursor::new(buf)).unwrap();
+
+        assert_eq!(result.duration, -5);
+        assert_eq!(result.duration_ms(), 0);
+        assert_eq!(
+            Pause {
+                duration: 3,
+                time: 0.0
+            }
+            .duration_ms(),
+            3000
+        );
+    }
+
+    #[test]
+    fn it_can_write_pauses() -> Result<()> {
+        let pauses = Vec::from([generate_random_pause(), generate_random_pause()]);
+
+        let mut buf = Vec::new();
+        Pauses::new(pauses.clone()).write(&mut buf)?;
+
+        assert_eq!(buf, get_pauses_buffer(&pauses)?);
+
+        Ok(())
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_pauses() {
         assert_eq!(Pauses::get_static_size(), 5);
     }
 
     #[test]
-    fn it_returns_invalid_bsor_error_when_pauses_block_id_is_invalid() -> Result<()> {
+    fn it_returns_unexpected_block_error_when_pauses_block_id_is_invalid() -> Result<()> {
         let pauses = Vec::from([generate_random_pause(), generate_random_pause()]);
 
         let mut buf = get_pauses_buffer(&pauses)?;
@@ -152,7 +279,10 @@ mod tests {
 
         let result = Pauses::load(&mut Cursor::new(buf));
 
-        assert!(matches!(result, Err(BsorError::InvalidBsor)));
+        assert!(matches!(
+            result,
+            Err(BsorError::UnexpectedBlock { found: 255, .. })
+        ));
 
         Ok(())
     }
@@ -195,4 +325,58 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn it_can_compute_total_duration() {
+        let pauses = Pauses::new(Vec::from([
+            Pause {
+                duration: 2,
+                time: 10.0,
+            },
+            Pause {
+                duration: 5,
+                time: 30.0,
+            },
+        ]));
+
+        assert_eq!(pauses.total_duration(), 7);
+        assert_eq!(Pauses::new(Vec::new()).total_duration(), 0);
+    }
+
+    #[test]
+    fn it_can_coalesce_adjacent_pauses() {
+        let pauses = Pauses::new(Vec::from([
+            Pause {
+                duration: 2,
+                time: 10.0,
+            },
+            Pause {
+                duration: 3,
+                time: 10.05,
+            },
+            Pause {
+                duration: 1,
+                time: 10.1,
+            },
+            Pause {
+                duration: 5,
+                time: 30.0,
+            },
+        ]));
+
+        assert_eq!(
+            pauses.coalesced(0.1),
+            vec![
+                Pause {
+                    duration: 6,
+                    time: 10.0
+                },
+                Pause {
+                    duration: 5,
+                    time: 30.0
+                },
+            ]
+        );
+        assert_eq!(pauses.coalesced(0.0).len(), 4);
+    }
 }
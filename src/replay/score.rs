@@ -0,0 +1,177 @@
+//! helpers used to reconstruct the score of a replay
+use crate::replay::note::{NoteCutInfo, NoteEventType, NoteScoringType};
+use crate::replay::ReplayFloat;
+
+/// Max score of a note cut with all the points for the pre-swing, post-swing and accuracy
+pub(crate) const MAX_CUT_SCORE: u32 = 115;
+const MAX_PRE_SWING_SCORE: u32 = 70;
+const MAX_POST_SWING_SCORE: u32 = 30;
+const MAX_ACCURACY_SCORE: u32 = 15;
+const BURST_SLIDER_ELEMENT_SCORE: u32 = 20;
+/// Cut distance to the center of the note at which accuracy score drops to 0
+const MAX_CUT_DISTANCE_TO_CENTER: ReplayFloat = 0.3;
+
+/// Returns max score possible to get for a note of given scoring type
+pub(crate) fn max_score(scoring_type: NoteScoringType) -> u32 {
+    match scoring_type {
+        NoteScoringType::NormalOld
+        | NoteScoringType::Normal
+        | NoteScoringType::SliderHead
+        | NoteScoringType::SliderTail => MAX_CUT_SCORE,
+        NoteScoringType::BurstSliderHead => MAX_PRE_SWING_SCORE + MAX_ACCURACY_SCORE,
+        NoteScoringType::BurstSliderElement => BURST_SLIDER_ELEMENT_SCORE,
+        NoteScoringType::Ignore | NoteScoringType::NoScore | NoteScoringType::Unknown => 0,
+    }
+}
+
+/// Returns pre-swing, post-swing and accuracy score of a good cut
+///
+/// Arc (slider) heads always get full post-swing and arc tails full pre-swing points, chain (burst slider) heads get no post-swing points and chain elements get a fixed score counted as accuracy
+pub(crate) fn cut_score(scoring_type: NoteScoringType, cut_info: &NoteCutInfo) -> (u32, u32, u32) {
+    let pre_swing = rating_score(cut_info.before_cut_rating, MAX_PRE_SWING_SCORE);
+    let post_swing = rating_score(cut_info.after_cut_rating, MAX_POST_SWING_SCORE);
+    let accuracy = rating_score(
+        1.0 - cut_info.cut_distance_to_center / MAX_CUT_DISTANCE_TO_CENTER,
+        MAX_ACCURACY_SCORE,
+    );
+
+    match scoring_type {
+        NoteScoringType::NormalOld | NoteScoringType::Normal => (pre_swing, post_swing, accuracy),
+        NoteScoringType::SliderHead => (pre_swing, MAX_POST_SWING_SCORE, accuracy),
+        NoteScoringType::SliderTail => (MAX_PRE_SWING_SCORE, post_swing, accuracy),
+        NoteScoringType::BurstSliderHead => (pre_swing, 0, accuracy),
+        NoteScoringType::BurstSliderElement => (0, 0, BURST_SLIDER_ELEMENT_SCORE),
+        NoteScoringType::Ignore | NoteScoringType::NoScore | NoteScoringType::Unknown => (0, 0, 0),
+    }
+}
+
+/// Energy the player starts the song with
+pub(crate) const INITIAL_ENERGY: ReplayFloat = 0.5;
+
+/// Returns energy change caused by a note event
+pub(crate) fn energy_change(event_type: NoteEventType) -> ReplayFloat {
+    match event_type {
+        NoteEventType::Good => 0.01,
+        NoteEventType::Bad => -0.1,
+        NoteEventType::Miss | NoteEventType::Bomb => -0.15,
+        NoteEventType::Unknown(_) => 0.0,
+    }
+}
+
+fn rating_score(rating: ReplayFloat, max: u32) -> u32 {
+    (rating.clamp(0.0, 1.0) * max as ReplayFloat).round() as u32
+}
+
+/// Combo multiplier working the same way as in the game
+///
+/// Multiplier starts at 1x and doubles (up to 8x) after `2 * multiplier` consecutive good cuts, any combo break halves it and resets the progress
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ScoreMultiplier {
+    multiplier: u8,
+    progress: u8,
+}
+
+impl ScoreMultiplier {
+    pub(crate) const MAX: u8 = 8;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            multiplier: 1,
+            progress: 0,
+        }
+    }
+
+    pub(crate) fn value(&self) -> u8 {
+        self.multiplier
+    }
+
+    pub(crate) fn increase(&mut self) {
+        if self.multiplier >= Self::MAX {
+            return;
+        }
+
+        self.progress += 1;
+
+        if self.progress >= self.multiplier * 2 {
+            self.multiplier *= 2;
+            self.progress = 0;
+        }
+    }
+
+    pub(crate) fn decrease(&mut self) {
+        self.multiplier = (self.multiplier / 2).max(1);
+        self.progress = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_util::generate_random_note_cut_info;
+
+    #[test]
+    fn it_can_compute_cut_score() {
+        let mut cut_info = generate_random_note_cut_info();
+        cut_info.before_cut_rating = 1.2;
+        cut_info.after_cut_rating = 0.5;
+        cut_info.cut_distance_to_center = 0.1;
+
+        assert_eq!(cut_score(NoteScoringType::Normal, &cut_info), (70, 15, 10));
+        assert_eq!(
+            cut_score(NoteScoringType::SliderHead, &cut_info),
+            (70, 30, 10)
+        );
+        assert_eq!(
+            cut_score(NoteScoringType::BurstSliderHead, &cut_info),
+            (70, 0, 10)
+        );
+        assert_eq!(
+            cut_score(NoteScoringType::BurstSliderElement, &cut_info),
+            (0, 0, 20)
+        );
+
+        cut_info.before_cut_rating = 0.5;
+        assert_eq!(
+            cut_score(NoteScoringType::SliderTail, &cut_info),
+            (70, 15, 10)
+        );
+        assert_eq!(cut_score(NoteScoringType::Normal, &cut_info), (35, 15, 10));
+    }
+
+    #[test]
+    fn it_returns_max_score_of_scoring_type() {
+        assert_eq!(max_score(NoteScoringType::Normal), 115);
+        assert_eq!(max_score(NoteScoringType::BurstSliderHead), 85);
+        assert_eq!(max_score(NoteScoringType::BurstSliderElement), 20);
+        assert_eq!(max_score(NoteScoringType::NoScore), 0);
+    }
+
+    #[test]
+    fn it_increases_multiplier_after_consecutive_cuts() {
+        let mut multiplier = ScoreMultiplier::new();
+        let mut values = Vec::new();
+
+        for _ in 0..16 {
+            multiplier.increase();
+            values.push(multiplier.value());
+        }
+
+        assert_eq!(values, vec![1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 4, 4, 4, 8, 8, 8]);
+    }
+
+    #[test]
+    fn it_decreases_multiplier_on_combo_break() {
+        let mut multiplier = ScoreMultiplier::new();
+        for _ in 0..6 {
+            multiplier.increase();
+        }
+        assert_eq!(multiplier.value(), 4);
+
+        multiplier.decrease();
+        assert_eq!(multiplier.value(), 2);
+
+        multiplier.decrease();
+        multiplier.decrease();
+        assert_eq!(multiplier.value(), 1);
+    }
+}
@@ -0,0 +1,97 @@
+//! newtypes distinguishing song time from durations
+use crate::replay::ReplayTime;
+use std::ops::{Add, Sub};
+
+/// Point in song time, in seconds since the start of the song
+#[derive(PartialEq, PartialOrd, Clone, Copy, Debug, Default)]
+pub struct SongTime(pub ReplayTime);
+
+/// Length of a time interval, in seconds
+#[derive(PartialEq, PartialOrd, Clone, Copy, Debug, Default)]
+pub struct Duration(pub ReplayTime);
+
+impl From<ReplayTime> for SongTime {
+    fn from(time: ReplayTime) -> Self {
+        SongTime(time)
+    }
+}
+
+impl From<SongTime> for ReplayTime {
+    fn from(time: SongTime) -> Self {
+        time.0
+    }
+}
+
+impl From<ReplayTime> for Duration {
+    fn from(duration: ReplayTime) -> Self {
+        Duration(duration)
+    }
+}
+
+impl From<Duration> for ReplayTime {
+    fn from(duration: Duration) -> Self {
+        duration.0
+    }
+}
+
+impl Sub for SongTime {
+    type Output = Duration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Duration(self.0 - rhs.0)
+    }
+}
+
+impl Add<Duration> for SongTime {
+    type Output = SongTime;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        SongTime(self.0 + rhs.0)
+    }
+}
+
+impl Sub<Duration> for SongTime {
+    type Output = SongTime;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        SongTime(self.0 - rhs.0)
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Duration(self.0 + rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_can_convert_song_time_and_duration_from_and_into_replay_time() {
+        let time = SongTime::from(1.5);
+        let duration: Duration = 0.5.into();
+
+        assert_eq!(time, SongTime(1.5));
+        assert_eq!(duration, Duration(0.5));
+        assert_eq!(ReplayTime::from(time), 1.5);
+
+        let raw: ReplayTime = duration.into();
+        assert_eq!(raw, 0.5);
+    }
+
+    #[test]
+    fn it_can_do_time_arithmetic() {
+        let start = SongTime(1.0);
+        let end = SongTime(3.5);
+
+        assert_eq!(end - start, Duration(2.5));
+        assert_eq!(start + Duration(2.5), end);
+        assert_eq!(end - Duration(2.5), start);
+        assert_eq!(Duration(1.0) + Duration(0.5), Duration(1.5));
+        assert!(start < end);
+    }
+}
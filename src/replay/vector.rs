@@ -1,8 +1,16 @@
 //! auxiliary structs storing data about vectors
-use crate::replay::{read_utils, BsorError, GetStaticBlockSize, ReplayFloat};
-use std::io::Read;
+use crate::replay::{
+    read_utils, write_utils, ApproxEq, BsorError, GetStaticBlockSize, ReplayFloat,
+};
+use std::io::{Read, Write};
+use std::ops::{Add, Mul, Sub};
 
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Vector3 {
     pub x: ReplayFloat,
     pub y: ReplayFloat,
@@ -19,6 +27,88 @@ impl Vector3 {
             z: vec[2],
         })
     }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<(), BsorError> {
+        write_utils::write_float(w, self.x)?;
+        write_utils::write_float(w, self.y)?;
+        write_utils::write_float(w, self.z)
+    }
+
+    /// Returns euclidean distance to other vector
+    pub fn distance(&self, other: &Vector3) -> ReplayFloat {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2))
+            .sqrt()
+    }
+
+    /// Returns dot product with other vector
+    pub fn dot(&self, other: &Vector3) -> ReplayFloat {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns cross product with other vector
+    pub fn cross(&self, other: &Vector3) -> Vector3 {
+        Vector3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// Returns length (magnitude) of the vector
+    pub fn length(&self) -> ReplayFloat {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns unit vector of the same direction, zero vector is returned unchanged
+    pub fn normalized(&self) -> Vector3 {
+        let len = self.length();
+        if len == 0.0 {
+            return self.clone();
+        }
+
+        self.clone() * (1.0 / len)
+    }
+
+    /// Linearly interpolates between this (`t` = 0) and other (`t` = 1) vector
+    pub fn lerp(&self, other: &Vector3, t: ReplayFloat) -> Vector3 {
+        self.clone() + (other.clone() - self.clone()) * t
+    }
+}
+
+impl Add for Vector3 {
+    type Output = Vector3;
+
+    fn add(self, other: Vector3) -> Vector3 {
+        Vector3 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Sub for Vector3 {
+    type Output = Vector3;
+
+    fn sub(self, other: Vector3) -> Vector3 {
+        Vector3 {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl Mul<ReplayFloat> for Vector3 {
+    type Output = Vector3;
+
+    fn mul(self, scalar: ReplayFloat) -> Vector3 {
+        Vector3 {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+        }
+    }
 }
 
 impl GetStaticBlockSize for Vector3 {
@@ -27,6 +117,12 @@ impl GetStaticBlockSize for Vector3 {
     }
 }
 
+impl ApproxEq for Vector3 {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.x.approx_eq(&other.x) && self.y.approx_eq(&other.y) && self.z.approx_eq(&other.z)
+    }
+}
+
 impl From<Vector4> for Vector3 {
     fn from(v: Vector4) -> Self {
         Self {
@@ -38,6 +134,11 @@ impl From<Vector4> for Vector3 {
 }
 
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Vector4 {
     pub x: ReplayFloat,
     pub y: ReplayFloat,
@@ -56,6 +157,121 @@ impl Vector4 {
             w: vec[3],
         })
     }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<(), BsorError> {
+        write_utils::write_float(w, self.x)?;
+        write_utils::write_float(w, self.y)?;
+        write_utils::write_float(w, self.z)?;
+        write_utils::write_float(w, self.w)
+    }
+
+    /// Converts rotation quaternion into Euler angles in radians (pitch, yaw, roll), using Unity rotation order (Z, X, Y)
+    ///
+    /// The quaternion is normalized first, see [Vector4::normalized()]
+    pub fn to_euler_angles(&self) -> (ReplayFloat, ReplayFloat, ReplayFloat) {
+        let Vector4 { x, y, z, w } = self.normalized();
+
+        (
+            (2.0 * (w * x - y * z)).clamp(-1.0, 1.0).asin(),
+            (2.0 * (w * y + x * z)).atan2(1.0 - 2.0 * (x * x + y * y)),
+            (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (x * x + z * z)),
+        )
+    }
+
+    /// Returns unit quaternion of the same rotation, zero quaternion is returned unchanged
+    pub fn normalized(&self) -> Vector4 {
+        let len = (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt();
+        if len == 0.0 {
+            return self.clone();
+        }
+
+        Vector4 {
+            x: self.x / len,
+            y: self.y / len,
+            z: self.z / len,
+            w: self.w / len,
+        }
+    }
+
+    /// Spherically interpolates between this (`t` = 0) and other (`t` = 1) rotation quaternion along the shortest path
+    ///
+    /// Both quaternions are normalized first, nearly identical rotations are interpolated linearly
+    pub fn slerp(&self, other: &Vector4, t: ReplayFloat) -> Vector4 {
+        let a = self.normalized();
+        let mut b = other.normalized();
+
+        let mut dot = a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w;
+        if dot < 0.0 {
+            b = Vector4 {
+                x: -b.x,
+                y: -b.y,
+                z: -b.z,
+                w: -b.w,
+            };
+            dot = -dot;
+        }
+
+        let (wa, wb) = if dot > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = dot.acos();
+            let sin_theta = theta.sin();
+
+            (
+                ((1.0 - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        Vector4 {
+            x: wa * a.x + wb * b.x,
+            y: wa * a.y + wb * b.y,
+            z: wa * a.z + wb * b.z,
+            w: wa * a.w + wb * b.w,
+        }
+        .normalized()
+    }
+
+    /// Converts rotation quaternion into the rotation axis (unit vector) and angle in radians
+    ///
+    /// For a rotation close to identity (angle ≈ 0) or a zero quaternion the axis is arbitrary, X axis is returned
+    pub fn to_axis_angle(&self) -> (Vector3, ReplayFloat) {
+        let Vector4 { x, y, z, w } = self.normalized();
+        if x == 0.0 && y == 0.0 && z == 0.0 && w == 0.0 {
+            return (
+                Vector3 {
+                    x: 1.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                0.0,
+            );
+        }
+
+        let w = w.clamp(-1.0, 1.0);
+        let angle = 2.0 * w.acos();
+        let s = (1.0 - w * w).sqrt();
+
+        if s < 1e-6 {
+            return (
+                Vector3 {
+                    x: 1.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                angle,
+            );
+        }
+
+        (
+            Vector3 {
+                x: x / s,
+                y: y / s,
+                z: z / s,
+            },
+            angle,
+        )
+    }
 }
 
 impl GetStaticBlockSize for Vector4 {
@@ -64,6 +280,15 @@ impl GetStaticBlockSize for Vector4 {
     }
 }
 
+impl ApproxEq for Vector4 {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.x.approx_eq(&other.x)
+            && self.y.approx_eq(&other.y)
+            && self.z.approx_eq(&other.z)
+            && self.w.approx_eq(&other.w)
+    }
+}
+
 impl From<Vector3> for Vector4 {
     fn from(v: Vector3) -> Self {
         Self {
@@ -115,6 +340,88 @@ mod tests {
         assert_eq!(floats[3], result.w);
     }
 
+    #[test]
+    fn it_can_compute_distance_between_vectors3() {
+        let v1 = Vector3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+        let v2 = Vector3 {
+            x: 4.0,
+            y: 6.0,
+            z: 3.0,
+        };
+
+        assert_eq!(v1.distance(&v2), 5.0);
+        assert_eq!(v2.distance(&v1), 5.0);
+        assert_eq!(v1.distance(&v1), 0.0);
+    }
+
+    #[test]
+    fn it_can_do_vector3_math() {
+        let x = Vector3 {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let y = Vector3 {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        let v = Vector3 {
+            x: 3.0,
+            y: 4.0,
+            z: 0.0,
+        };
+
+        assert_eq!(x.dot(&y), 0.0);
+        assert_eq!(
+            x.cross(&y),
+            Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0
+            }
+        );
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(
+            v.normalized(),
+            Vector3 {
+                x: 0.6,
+                y: 0.8,
+                z: 0.0
+            }
+        );
+        assert_eq!((v.clone() * 0.0).normalized(), v.clone() * 0.0);
+
+        assert_eq!(
+            v.clone() + x.clone(),
+            Vector3 {
+                x: 4.0,
+                y: 4.0,
+                z: 0.0
+            }
+        );
+        assert_eq!(
+            v.clone() - y.clone(),
+            Vector3 {
+                x: 3.0,
+                y: 3.0,
+                z: 0.0
+            }
+        );
+        assert_eq!(
+            v * 2.0,
+            Vector3 {
+                x: 6.0,
+                y: 8.0,
+                z: 0.0
+            }
+        );
+    }
+
     #[test]
     fn it_can_convert_vector3_to_vector4() {
         let v3 = Vector3 {
@@ -146,4 +453,181 @@ mod tests {
         assert_eq!(v3.y, v4.y);
         assert_eq!(v3.z, v4.z);
     }
+
+    #[test]
+    fn it_can_convert_vector4_to_axis_angle() {
+        let half = std::f32::consts::FRAC_PI_4;
+        let v4 = Vector4 {
+            x: half.sin(),
+            y: 0.0,
+            z: 0.0,
+            w: half.cos(),
+        };
+
+        let (axis, angle) = v4.to_axis_angle();
+
+        assert!((axis.x - 1.0).abs() < 1e-6);
+        assert!(axis.y.abs() < 1e-6);
+        assert!(axis.z.abs() < 1e-6);
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn it_returns_arbitrary_axis_for_identity_rotation() {
+        let v4 = Vector4 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        };
+
+        let (axis, angle) = v4.to_axis_angle();
+
+        assert_eq!(
+            axis,
+            Vector3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0
+            }
+        );
+        assert_eq!(angle, 0.0);
+    }
+
+    #[test]
+    fn it_can_convert_quaternion_to_euler_angles() {
+        let half = std::f32::consts::FRAC_PI_4 / 2.0;
+
+        let yaw = Vector4 {
+            x: 0.0,
+            y: half.sin(),
+            z: 0.0,
+            w: half.cos(),
+        }
+        .to_euler_angles();
+        assert!((yaw.1 - std::f32::consts::FRAC_PI_4).abs() < 1e-5);
+        assert!(yaw.0.abs() < 1e-5 && yaw.2.abs() < 1e-5);
+
+        let pitch = Vector4 {
+            x: half.sin(),
+            y: 0.0,
+            z: 0.0,
+            w: half.cos(),
+        }
+        .to_euler_angles();
+        assert!((pitch.0 - std::f32::consts::FRAC_PI_4).abs() < 1e-5);
+        assert!(pitch.1.abs() < 1e-5 && pitch.2.abs() < 1e-5);
+    }
+
+    #[test]
+    fn it_can_convert_identity_and_right_angle_yaw_to_euler_angles() {
+        let identity = Vector4 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        };
+        assert_eq!(identity.to_euler_angles(), (0.0, 0.0, 0.0));
+
+        // not normalized 90° yaw
+        let half = std::f32::consts::FRAC_PI_4;
+        let yaw = Vector4 {
+            x: 0.0,
+            y: half.sin() * 2.0,
+            z: 0.0,
+            w: half.cos() * 2.0,
+        }
+        .to_euler_angles();
+        assert!((yaw.1 - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+        assert!(yaw.0.abs() < 1e-5 && yaw.2.abs() < 1e-5);
+    }
+
+    #[test]
+    fn it_can_normalize_quaternion() {
+        let q = Vector4 {
+            x: 0.0,
+            y: 3.0,
+            z: 0.0,
+            w: 4.0,
+        };
+
+        assert_eq!(
+            q.normalized(),
+            Vector4 {
+                x: 0.0,
+                y: 0.6,
+                z: 0.0,
+                w: 0.8
+            }
+        );
+
+        let zero = Vector4 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 0.0,
+        };
+        assert_eq!(zero.normalized(), zero);
+    }
+
+    #[test]
+    fn it_can_interpolate_vectors() {
+        let a = Vector3 {
+            x: 0.0,
+            y: 2.0,
+            z: -4.0,
+        };
+        let b = Vector3 {
+            x: 2.0,
+            y: 4.0,
+            z: 4.0,
+        };
+
+        assert_eq!(a.lerp(&b, 0.0), a);
+        assert_eq!(a.lerp(&b, 1.0), b);
+        assert_eq!(
+            a.lerp(&b, 0.25),
+            Vector3 {
+                x: 0.5,
+                y: 2.5,
+                z: -2.0
+            }
+        );
+    }
+
+    #[test]
+    fn it_can_slerp_quaternions() {
+        let identity = Vector4 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        };
+        let half_sqrt = std::f32::consts::FRAC_1_SQRT_2;
+        let yaw_90 = Vector4 {
+            x: 0.0,
+            y: half_sqrt,
+            z: 0.0,
+            w: half_sqrt,
+        };
+
+        let mid = identity.slerp(&yaw_90, 0.5);
+        assert!(mid.x.abs() < 0.0001);
+        assert!((mid.y - 0.382_683).abs() < 0.0001);
+        assert!(mid.z.abs() < 0.0001);
+        assert!((mid.w - 0.923_880).abs() < 0.0001);
+
+        // the same rotation with negated quaternion takes the shortest path too
+        let negated = Vector4 {
+            x: 0.0,
+            y: -half_sqrt,
+            z: 0.0,
+            w: -half_sqrt,
+        };
+        let mid = identity.slerp(&negated, 0.5);
+        assert!((mid.y - 0.382_683).abs() < 0.0001);
+        assert!((mid.w - 0.923_880).abs() < 0.0001);
+
+        assert_eq!(identity.slerp(&identity, 0.3), identity);
+    }
 }
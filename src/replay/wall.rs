@@ -1,38 +1,49 @@
 //! structs storing the Walls block data
-use super::{read_utils, ReplayTime, Result};
+use super::{read_utils, write_utils, ReplayTime, Result};
 use crate::replay::{
-    assert_start_of_block, BlockIndex, BlockType, GetStaticBlockSize, LineIdx, LoadBlock,
-    LoadRealBlockSize, ReplayFloat, ReplayInt,
+    expect_block, load_block_items, write_block_items, ApproxEq, BlockIndex, BlockType,
+    GetStaticBlockSize, LineIdx, LoadBlock, LoadOptions, LoadRealBlockSize, LoadWarning,
+    ReplayFloat, ReplayInt,
 };
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::marker::PhantomData;
 use std::mem::size_of;
 use std::ops::Deref;
 
 /// Struct implements [std::ops::Deref] trait so it could be treated as Vec<[Wall]>
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Walls(Vec<Wall>);
 
 impl Walls {
-    pub(crate) fn load<R: Read>(r: &mut R) -> Result<Walls> {
-        assert_start_of_block(r, BlockType::Walls)?;
+    pub(crate) fn load<RS: Read + Seek>(r: &mut RS) -> Result<Walls> {
+        let offset = r.stream_position()?;
 
-        let count = read_utils::read_int(r)? as usize;
-        let mut vec = Vec::<Wall>::with_capacity(count);
-
-        for _ in 0..count {
-            vec.push(Wall::load(r)?);
-        }
+        Self::load_with_options(r, offset, &LoadOptions::default(), &mut Vec::new())
+    }
 
-        Ok(Walls(vec))
+    pub(crate) fn load_with_options<R: Read>(
+        r: &mut R,
+        offset: u64,
+        options: &LoadOptions,
+        warnings: &mut Vec<LoadWarning>,
+    ) -> Result<Walls> {
+        Ok(Walls(load_block_items(
+            r,
+            BlockType::Walls,
+            offset,
+            options,
+            warnings,
+            Wall::load,
+        )?))
     }
 
-    #[cfg(test)]
-    pub(crate) fn new(vec: Vec<Wall>) -> Walls {
+    /// Creates block from given items
+    pub fn new(vec: Vec<Wall>) -> Walls {
         Walls(vec)
     }
 
-    /// Loads Frames block from ReplayIndex
+    /// Loads Walls block from ReplayIndex
     pub(crate) fn load_block<RS: Read + Seek>(
         r: &mut RS,
         block: &BlockIndex<Walls>,
@@ -41,6 +52,11 @@ impl Walls {
 
         Self::load(r)
     }
+
+    /// Writes the block id, items count and all items
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        write_block_items(w, BlockType::Walls, &self.0, Wall::write)
+    }
 }
 
 impl Deref for Walls {
@@ -65,11 +81,13 @@ impl LoadBlock for BlockIndex<Walls> {
     }
 }
 
+impl_block!(Walls, Wall, [time, spawn_time]);
+
 impl LoadRealBlockSize for Walls {
     type Item = Walls;
 
     fn load_real_block_size<RS: Read + Seek>(r: &mut RS, pos: u64) -> Result<BlockIndex<Walls>> {
-        assert_start_of_block(r, BlockType::Walls)?;
+        expect_block(r, BlockType::Walls, pos)?;
 
         let count = read_utils::read_int(r)?;
 
@@ -82,13 +100,21 @@ impl LoadRealBlockSize for Walls {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Wall {
     pub line_idx: LineIdx,
     pub obstacle_type: u8,
     pub width: u8,
+    /// energy left after hitting the wall
     pub energy: ReplayFloat,
+    /// song time the wall was hit at, in seconds
     pub time: ReplayTime,
+    /// song time the wall spawned at, in seconds
     pub spawn_time: ReplayTime,
 }
 
@@ -117,14 +143,77 @@ impl Wall {
             spawn_time,
         })
     }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        let wall_id = self.line_idx as ReplayInt * 100
+            + self.obstacle_type as ReplayInt * 10
+            + self.width as ReplayInt;
+
+        write_utils::write_int(w, wall_id)?;
+        write_utils::write_float(w, self.energy)?;
+        write_utils::write_float(w, self.time)?;
+        write_utils::write_float(w, self.spawn_time)
+    }
+
+    /// Returns whether the wall spans the whole height of the grid, i.e. has to be dodged sideways
+    pub fn is_full_height(&self) -> bool {
+        self.obstacle_type == OBSTACLE_FULL_HEIGHT
+    }
+
+    /// Returns whether the wall hangs from the top of the grid, i.e. has to be crouched under
+    pub fn is_crouch_wall(&self) -> bool {
+        self.obstacle_type == OBSTACLE_CROUCH
+    }
+
+    /// Returns width of the wall in meters, see [LANE_WIDTH]
+    pub fn width_meters(&self) -> f32 {
+        self.width as f32 * LANE_WIDTH
+    }
+
+    /// Returns approximate wall extent in meters as (x min, x max, y min, y max)
+    ///
+    /// X is measured from the center of the 4-lane grid (negative to the left) with lanes [LANE_WIDTH] wide, Y from the floor with grid layers [LAYER_HEIGHT] high. Full height walls span 5 layers from the floor, crouch walls 3 layers from the third layer, other (custom) obstacle types are treated as full height. Wall duration is not stored in the replay, so the extent in time is not known
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
+        let x_min = (self.line_idx as f32 - 2.0) * LANE_WIDTH;
+        let (y_min, y_max) = if self.is_crouch_wall() {
+            (2.0 * LAYER_HEIGHT, 5.0 * LAYER_HEIGHT)
+        } else {
+            (0.0, 5.0 * LAYER_HEIGHT)
+        };
+
+        (x_min, x_min + self.width_meters(), y_min, y_max)
+    }
 }
 
+/// Obstacle type of walls spanning the whole height of the grid
+pub const OBSTACLE_FULL_HEIGHT: u8 = 0;
+
+/// Obstacle type of walls hanging from the top of the grid
+pub const OBSTACLE_CROUCH: u8 = 1;
+
+/// Approximate width of a single grid lane in meters, see [Wall::bounds()]
+pub const LANE_WIDTH: f32 = 0.6;
+
+/// Approximate height of a single grid layer in meters, see [Wall::bounds()]
+pub const LAYER_HEIGHT: f32 = 0.6;
+
 impl GetStaticBlockSize for Wall {
     fn get_static_size() -> usize {
         size_of::<ReplayInt>() + size_of::<ReplayFloat>() * 3
     }
 }
 
+impl ApproxEq for Wall {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.line_idx == other.line_idx
+            && self.obstacle_type == other.obstacle_type
+            && self.width == other.width
+            && self.energy.approx_eq(&other.energy)
+            && self.time.approx_eq(&other.time)
+            && self.spawn_time.approx_eq(&other.spawn_time)
+    }
+}
+
 impl LoadRealBlockSize for Wall {
     type Item = Wall;
 }
@@ -136,6 +225,18 @@ mod tests {
     use crate::tests_util::{append_wall, generate_random_wall, get_walls_buffer};
     use std::io::Cursor;
 
+    #[test]
+    fn it_can_access_walls_by_index_and_get_vec() {
+        let items = vec![generate_random_wall(), generate_random_wall()];
+        let walls = Walls(items.clone());
+
+        assert_eq!(walls.len(), 2);
+        assert!(!walls.is_empty());
+        assert_eq!(walls[1], items[1]);
+        assert_eq!(walls.get_vec(), &items);
+        assert!(Walls(Vec::new()).get_vec().is_empty());
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_wall() {
         assert_eq!(Wall::get_static_size(), 16);
@@ -153,13 +254,67 @@ mod tests {
         assert_eq!(result, wall)
     }
 
+    #[test]
+    fn it_can_compute_wall_geometry() -> Result<()> {
+        let load = |wall_id: ReplayInt| {
+            let mut buf = ReplayInt::to_le_bytes(wall_id).to_vec();
+            buf.append(&mut [0u8; 12].to_vec());
+
+            Wall::load(&mut Cursor::new(buf))
+        };
+        let assert_bounds = |wall: &Wall, expected: (f32, f32, f32, f32)| {
+            let (x_min, x_max, y_min, y_max) = wall.bounds();
+
+            assert!((x_min - expected.0).abs() < 0.0001);
+            assert!((x_max - expected.1).abs() < 0.0001);
+            assert!((y_min - expected.2).abs() < 0.0001);
+            assert!((y_max - expected.3).abs() < 0.0001);
+        };
+
+        // full height wall in the leftmost lane, 1 lane wide
+        let wall = load(1)?;
+        assert!(wall.is_full_height());
+        assert!(!wall.is_crouch_wall());
+        assert!((wall.width_meters() - 0.6).abs() < 0.0001);
+        assert_bounds(&wall, (-1.2, -0.6, 0.0, 3.0));
+
+        // crouch wall over the whole grid
+        let wall = load(14)?;
+        assert!(wall.is_crouch_wall());
+        assert!(!wall.is_full_height());
+        assert_bounds(&wall, (-1.2, 1.2, 1.2, 3.0));
+
+        // full height wall in the third lane, 2 lanes wide
+        let wall = load(202)?;
+        assert!(wall.is_full_height());
+        assert_bounds(&wall, (0.0, 1.2, 0.0, 3.0));
+
+        let wall = load(321)?;
+        assert!(!wall.is_full_height());
+        assert!(!wall.is_crouch_wall());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_write_walls() -> Result<()> {
+        let walls = Vec::from([generate_random_wall(), generate_random_wall()]);
+
+        let mut buf = Vec::new();
+        Walls::new(walls.clone()).write(&mut buf)?;
+
+        assert_eq!(buf, get_walls_buffer(&walls)?);
+
+        Ok(())
+    }
+
     #[test]
     fn it_returns_correct_static_size_of_walls() {
         assert_eq!(Walls::get_static_size(), 5);
     }
 
     #[test]
-    fn it_returns_invalid_bsor_error_when_walls_block_id_is_invalid() -> Result<()> {
+    fn it_returns_unexpected_block_error_when_walls_block_id_is_invalid() -> Result<()> {
         let walls = Vec::from([generate_random_wall(), generate_random_wall()]);
 
         let mut buf = get_walls_buffer(&walls)?;
@@ -167,7 +322,10 @@ mod tests {
 
         let result = Walls::load(&mut Cursor::new(buf));
 
-        assert!(matches!(result, Err(BsorError::InvalidBsor)));
+        assert!(matches!(
+            result,
+            Err(BsorError::UnexpectedBlock { found: 255, .. })
+        ));
 
         Ok(())
     }
@@ -0,0 +1,66 @@
+use crate::replay::{ReplayFloat, ReplayInt, ReplayLong, Result};
+use std::io::Write;
+
+pub(crate) fn write_byte<W: Write>(w: &mut W, v: u8) -> Result<()> {
+    w.write_all(&[v])?;
+
+    Ok(())
+}
+
+pub(crate) fn write_bool<W: Write>(w: &mut W, v: bool) -> Result<()> {
+    write_byte(w, v as u8)
+}
+
+pub(crate) fn write_int<W: Write>(w: &mut W, v: ReplayInt) -> Result<()> {
+    w.write_all(&ReplayInt::to_le_bytes(v))?;
+
+    Ok(())
+}
+
+pub(crate) fn write_long<W: Write>(w: &mut W, v: ReplayLong) -> Result<()> {
+    w.write_all(&ReplayLong::to_le_bytes(v))?;
+
+    Ok(())
+}
+
+pub(crate) fn write_float<W: Write>(w: &mut W, v: ReplayFloat) -> Result<()> {
+    w.write_all(&ReplayFloat::to_le_bytes(v))?;
+
+    Ok(())
+}
+
+pub(crate) fn write_string<W: Write>(w: &mut W, v: &str) -> Result<()> {
+    write_int(w, v.len() as ReplayInt)?;
+    w.write_all(v.as_bytes())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::read_utils;
+    use std::io::Cursor;
+
+    #[test]
+    fn it_can_write_primitives() -> Result<()> {
+        let mut buf = Vec::new();
+
+        write_byte(&mut buf, 7)?;
+        write_bool(&mut buf, true)?;
+        write_int(&mut buf, -123456)?;
+        write_long(&mut buf, 1 << 40)?;
+        write_float(&mut buf, 1.75)?;
+        write_string(&mut buf, "test_str")?;
+
+        let r = &mut Cursor::new(buf);
+        assert_eq!(read_utils::read_byte(r)?, 7);
+        assert!(read_utils::read_bool(r)?);
+        assert_eq!(read_utils::read_int(r)?, -123456);
+        assert_eq!(read_utils::read_long(r)?, 1 << 40);
+        assert_eq!(read_utils::read_float(r)?, 1.75);
+        assert_eq!(read_utils::read_string(r)?, "test_str");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,345 @@
+//! A module for writing a replay, optionally replacing its individual blocks
+//!
+//! # Example
+//! Replacing the Notes block of an indexed replay, all other blocks are copied verbatim from the source:
+//! ```no_run
+//! use bsor::prelude::*;
+//! use bsor::replay::note::Notes;
+//! use bsor::replay::writer::ReplayWriter;
+//! use std::fs::File;
+//! use std::io::{BufReader, BufWriter};
+//!
+//! let br = &mut BufReader::new(File::open("example.bsor").unwrap());
+//! let replay_index = ReplayIndex::index(br).unwrap();
+//!
+//! let notes = replay_index.notes.load(br).unwrap();
+//! let good_notes = notes.iter().filter(|n| n.cut_info.is_some()).cloned().collect();
+//!
+//! let bw = &mut BufWriter::new(File::create("edited.bsor").unwrap());
+//! ReplayWriter::from_index(&replay_index, br)
+//!     .with_notes(Notes::new(good_notes))
+//!     .write(bw)
+//!     .unwrap();
+//! ```
+use super::frame::Frames;
+use super::header::Header;
+use super::height::Heights;
+use super::info::Info;
+use super::note::Notes;
+use super::pause::Pauses;
+use super::wall::Walls;
+use super::{BlockIndex, BsorError, GetStaticBlockSize, Replay, ReplayIndex, Result};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+/// Writes a replay loaded into memory ([ReplayWriter::new()]) or an indexed one ([ReplayWriter::from_index()]), replacing blocks set with `with_*` methods
+///
+/// When writing from an index, unchanged blocks are copied verbatim from the source reader without being parsed
+pub struct ReplayWriter<'a, RS: Read + Seek = io::Empty> {
+    source: ReplaySource<'a, RS>,
+    info: Option<Info>,
+    frames: Option<Frames>,
+    notes: Option<Notes>,
+    walls: Option<Walls>,
+    heights: Option<Heights>,
+    pauses: Option<Pauses>,
+}
+
+enum ReplaySource<'a, RS: Read + Seek> {
+    Replay(&'a Replay),
+    Index(&'a ReplayIndex, &'a mut RS),
+}
+
+impl<'a> ReplayWriter<'a> {
+    /// Creates writer using replay loaded into memory as the source of unchanged blocks
+    pub fn new(replay: &'a Replay) -> Self {
+        Self::from_source(ReplaySource::Replay(replay))
+    }
+}
+
+impl<'a, RS: Read + Seek> ReplayWriter<'a, RS> {
+    /// Creates writer using indexed replay as the source of unchanged blocks, `r` must be the reader the replay was indexed from
+    pub fn from_index(index: &'a ReplayIndex, r: &'a mut RS) -> Self {
+        Self::from_source(ReplaySource::Index(index, r))
+    }
+
+    fn from_source(source: ReplaySource<'a, RS>) -> Self {
+        Self {
+            source,
+            info: None,
+            frames: None,
+            notes: None,
+            walls: None,
+            heights: None,
+            pauses: None,
+        }
+    }
+
+    /// Replaces Info block
+    pub fn with_info(mut self, info: Info) -> Self {
+        self.info = Some(info);
+        self
+    }
+
+    /// Replaces Frames block
+    pub fn with_frames(mut self, frames: Frames) -> Self {
+        self.frames = Some(frames);
+        self
+    }
+
+    /// Replaces Notes block
+    pub fn with_notes(mut self, notes: Notes) -> Self {
+        self.notes = Some(notes);
+        self
+    }
+
+    /// Replaces Walls block
+    pub fn with_walls(mut self, walls: Walls) -> Self {
+        self.walls = Some(walls);
+        self
+    }
+
+    /// Replaces Heights block
+    pub fn with_heights(mut self, heights: Heights) -> Self {
+        self.heights = Some(heights);
+        self
+    }
+
+    /// Replaces Pauses block
+    pub fn with_pauses(mut self, pauses: Pauses) -> Self {
+        self.pauses = Some(pauses);
+        self
+    }
+
+    /// Writes the whole replay
+    pub fn write<W: Write>(self, w: &mut W) -> Result<()> {
+        let Self {
+            mut source,
+            info,
+            frames,
+            notes,
+            walls,
+            heights,
+            pauses,
+        } = self;
+
+        let version = match &source {
+            ReplaySource::Replay(replay) => replay.version,
+            ReplaySource::Index(index, _) => index.version,
+        };
+        Header { version }.write(w)?;
+
+        match (&info, &mut source) {
+            (Some(info), _) => info.write(w)?,
+            (None, ReplaySource::Replay(replay)) => replay.info.write(w)?,
+            (None, ReplaySource::Index(index, r)) => {
+                let pos = Header::get_static_size() as u64;
+                copy_bytes(*r, pos, index.frames.pos - pos, w)?
+            }
+        }
+
+        source.write_block(
+            w,
+            frames.as_ref(),
+            |r| &r.frames,
+            |i| &i.frames,
+            Frames::write,
+        )?;
+        source.write_block(w, notes.as_ref(), |r| &r.notes, |i| &i.notes, Notes::write)?;
+        source.write_block(w, walls.as_ref(), |r| &r.walls, |i| &i.walls, Walls::write)?;
+        source.write_block(
+            w,
+            heights.as_ref(),
+            |r| &r.heights,
+            |i| &i.heights,
+            Heights::write,
+        )?;
+        source.write_block(
+            w,
+            pauses.as_ref(),
+            |r| &r.pauses,
+            |i| &i.pauses,
+            Pauses::write,
+        )?;
+
+        Ok(())
+    }
+}
+
+impl<RS: Read + Seek> ReplaySource<'_, RS> {
+    fn write_block<W: Write, T>(
+        &mut self,
+        w: &mut W,
+        replacement: Option<&T>,
+        from_replay: fn(&Replay) -> &T,
+        from_index: fn(&ReplayIndex) -> &BlockIndex<T>,
+        write: fn(&T, &mut W) -> Result<()>,
+    ) -> Result<()> {
+        match (replacement, self) {
+            (Some(block), _) => write(block, w),
+            (None, ReplaySource::Replay(replay)) => write(from_replay(replay), w),
+            (None, ReplaySource::Index(index, r)) => {
+                let block = from_index(index);
+                copy_bytes(*r, block.pos, block.bytes, w)
+            }
+        }
+    }
+}
+
+fn copy_bytes<RS: Read + Seek, W: Write>(
+    r: &mut RS,
+    pos: u64,
+    bytes: u64,
+    w: &mut W,
+) -> Result<()> {
+    r.seek(SeekFrom::Start(pos))?;
+
+    if io::copy(&mut r.take(bytes), w)? != bytes {
+        return Err(BsorError::InvalidBsor);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::note::{ColorType, CutDirection, NoteEventType};
+    use crate::replay::LoadBlock;
+    use crate::tests_util::{
+        generate_bomb_with_id, generate_random_note, generate_random_replay, get_replay_buffer,
+    };
+    use std::io::Cursor;
+
+    #[test]
+    fn it_can_write_replay() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let mut buf = Vec::new();
+        ReplayWriter::new(&replay).write(&mut buf)?;
+
+        assert_eq!(buf, get_replay_buffer(&replay)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_write_indexed_replay_verbatim() -> Result<()> {
+        let replay = generate_random_replay();
+        let source = get_replay_buffer(&replay)?;
+
+        let reader = &mut Cursor::new(source.clone());
+        let index = ReplayIndex::index(reader)?;
+
+        let mut buf = Vec::new();
+        ReplayWriter::from_index(&index, reader).write(&mut buf)?;
+
+        assert_eq!(buf, source);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_replace_notes_of_indexed_replay() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let reader = &mut Cursor::new(get_replay_buffer(&replay)?);
+        let index = ReplayIndex::index(reader)?;
+
+        let notes = Notes::new(Vec::from([
+            generate_random_note(NoteEventType::Good),
+            generate_random_note(NoteEventType::Miss),
+            generate_random_note(NoteEventType::Bad),
+        ]));
+
+        let mut buf = Vec::new();
+        ReplayWriter::from_index(&index, reader)
+            .with_notes(notes.clone())
+            .write(&mut buf)?;
+
+        let result = Replay::load(&mut Cursor::new(buf))?;
+
+        assert_eq!(result.info, replay.info);
+        assert_eq!(result.frames, replay.frames);
+        assert_eq!(result.notes, notes);
+        assert_eq!(result.walls, replay.walls);
+        assert_eq!(result.heights, replay.heights);
+        assert_eq!(result.pauses, replay.pauses);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_keeps_raw_note_ids_when_rewriting_notes() -> Result<()> {
+        let notes = Notes::new(Vec::from([
+            generate_random_note(NoteEventType::Good),
+            generate_bomb_with_id(1999),
+        ]));
+        let replay = Replay {
+            notes,
+            ..generate_random_replay()
+        };
+        let source = get_replay_buffer(&replay)?;
+
+        let reader = &mut Cursor::new(source.clone());
+        let index = ReplayIndex::index(reader)?;
+        let notes = index.notes.load(reader)?;
+
+        let mut buf = Vec::new();
+        ReplayWriter::from_index(&index, reader)
+            .with_notes(notes)
+            .write(&mut buf)?;
+
+        assert_eq!(buf, source);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_encodes_edited_note_fields_when_rewriting_notes() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let reader = &mut Cursor::new(get_replay_buffer(&replay)?);
+        let index = ReplayIndex::index(reader)?;
+
+        let mut note = generate_random_note(NoteEventType::Good);
+        note.line_idx = 3;
+        note.line_layer = 2;
+        note.color_type = ColorType::Blue;
+        note.cut_direction = CutDirection::Dot;
+
+        let mut buf = Vec::new();
+        ReplayWriter::from_index(&index, reader)
+            .with_notes(Notes::new(vec![note]))
+            .write(&mut buf)?;
+
+        let result = Replay::load(&mut Cursor::new(buf))?;
+
+        assert_eq!(result.notes[0].note_id, 30000 + 3218);
+        assert_eq!(result.notes[0].line_idx, 3);
+        assert_eq!(result.notes[0].line_layer, 2);
+        assert_eq!(result.notes[0].color_type, ColorType::Blue);
+        assert_eq!(result.notes[0].cut_direction, CutDirection::Dot);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_replace_info_of_loaded_replay() -> Result<()> {
+        let replay = generate_random_replay();
+
+        let mut info = replay.info.clone();
+        info.player_name = "Player".to_owned();
+
+        let mut buf = Vec::new();
+        ReplayWriter::new(&replay)
+            .with_info(info.clone())
+            .write(&mut buf)?;
+
+        let result = Replay::load(&mut Cursor::new(buf))?;
+
+        assert_eq!(result.info, info);
+        assert_eq!(result.notes, replay.notes);
+
+        Ok(())
+    }
+}